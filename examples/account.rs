@@ -1,5 +1,5 @@
 use futures_util::stream::StreamExt;
-use helium_api::{accounts, models::QueryTimeRange, Client, DEFAULT_BASE_URL};
+use helium_api::{accounts, models::QueryActivity, Client, DEFAULT_BASE_URL};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -12,9 +12,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     .await?;
     println!("Account: {:?}", account);
 
-    let params = QueryTimeRange {
-        min_time: "-30 day".into(),
-        max_time: "-1 hour".into(),
+    let params = QueryActivity {
+        min_time: Some("-30 day".into()),
+        max_time: Some("-1 hour".into()),
+        ..Default::default()
     };
 
     let mut account_activity_stream = accounts::activity(
@@ -25,7 +25,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         rewards
             .iter()
             .take(10)
-            .for_each(|r| println!("Block: {}: {} HNT", r.block, r.amount.to_string()));
+            .for_each(|r| println!("Block: {}: {} HNT", r.block, r.amount));
     };
 
     Ok(())
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{fs, io, path::Path};
+
+/// The data served by a mock server.
+///
+/// Entries are kept as JSON in the format served by blockchain-http, so any
+/// field the server does not look at is passed through as is. The server
+/// relies on the following fields to look up and relate entries:
+///
+/// * accounts, hotspots and validators: `address`, and `owner` for hotspots
+///   and validators
+/// * ouis: `oui` and `owner`
+/// * blocks: `height` and `hash`
+/// * transactions: `hash`, `height` and, optionally, `block_hash`
+/// * oracle prices: `block`
+/// * transactions, for activity filters: `type` and `time` in seconds
+/// * hotspot and validator rewards: `gateway`, `account`, `amount` in bones
+///   and `timestamp`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MockData {
+    pub accounts: Vec<Value>,
+    pub hotspots: Vec<Value>,
+    pub validators: Vec<Value>,
+    pub validator_rewards: Vec<Value>,
+    /// Rewards earned by hotspots
+    pub rewards: Vec<Value>,
+    pub ouis: Vec<Value>,
+    /// Block descriptions, ordered from the newest block
+    pub blocks: Vec<Value>,
+    pub transactions: Vec<Value>,
+    /// Oracle prices, ordered from the newest price
+    pub oracle_prices: Vec<Value>,
+    pub oracle_predictions: Vec<Value>,
+    pub vars: Map<String, Value>,
+    /// The chain height. Defaults to the height of the newest block.
+    pub height: Option<u64>,
+}
+
+impl MockData {
+    /// Load mock data from a JSON file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let data = fs::read(path)?;
+        serde_json::from_slice(&data).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// The chain height served by `/blocks/height`.
+    pub fn height(&self) -> u64 {
+        self.height
+            .or_else(|| {
+                self.blocks
+                    .iter()
+                    .filter_map(|b| u64_field(b, "height"))
+                    .max()
+            })
+            .unwrap_or(1)
+    }
+}
+
+pub(crate) fn str_field<'a>(value: &'a Value, field: &str) -> Option<&'a str> {
+    value.get(field).and_then(Value::as_str)
+}
+
+pub(crate) fn u64_field(value: &Value, field: &str) -> Option<u64> {
+    value.get(field).and_then(Value::as_u64)
+}
+
+/// Whether the given address appears anywhere in the given value.
+pub(crate) fn involves(value: &Value, address: &str) -> bool {
+    match value {
+        Value::String(s) => s == address,
+        Value::Array(values) => values.iter().any(|v| involves(v, address)),
+        Value::Object(map) => map.values().any(|v| involves(v, address)),
+        _ => false,
+    }
+}
@@ -0,0 +1,133 @@
+use crate::data::{str_field, u64_field};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The time range and transaction types of an activity or rewards query.
+///
+/// The live API ignores the query of a request with a cursor, since the
+/// cursor encodes the query it was issued for. The mock does the same by
+/// carrying the resolved filter in its cursors, see `Filter::resolve`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Filter {
+    /// The inclusive start of the time range, in seconds since the epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_time: Option<i64>,
+    /// The exclusive end of the time range, in seconds since the epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_time: Option<i64>,
+    /// The transaction types to keep, or all types if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    types: Vec<String>,
+}
+
+/// The query params of an activity or rewards request.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct FilterQuery {
+    cursor: Option<String>,
+    min_time: Option<String>,
+    max_time: Option<String>,
+    filter_types: Option<String>,
+}
+
+impl Filter {
+    /// Resolve the filter of a request, returning it with the paging part
+    /// of the cursor, if any. The filter is taken from the cursor when one is
+    /// given and from the query params otherwise.
+    pub(crate) fn resolve(query: &FilterQuery) -> Result<(Self, Option<String>), String> {
+        if let Some(cursor) = &query.cursor {
+            return match cursor.split_once('~') {
+                Some((offset, filter)) => serde_json::from_str(filter)
+                    .map(|filter| (filter, Some(offset.to_string())))
+                    .map_err(|_| "invalid cursor".to_string()),
+                None => Ok((Self::default(), Some(cursor.clone()))),
+            };
+        }
+        let now = Utc::now();
+        let time = |time: &Option<String>| {
+            time.as_deref()
+                .map(|time| parse_time(time, now).map(|time| time.timestamp()))
+                .transpose()
+        };
+        let types = query
+            .filter_types
+            .as_deref()
+            .map(|types| {
+                types
+                    .split(',')
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let filter = Self {
+            min_time: time(&query.min_time)?,
+            max_time: time(&query.max_time)?,
+            types,
+        };
+        Ok((filter, None))
+    }
+
+    /// Whether both ends of the time range are given.
+    pub(crate) fn time_range(&self) -> Option<(i64, i64)> {
+        Some((self.min_time?, self.max_time?))
+    }
+
+    /// The suffix of the cursors served for this filter.
+    pub(crate) fn cursor_suffix(&self) -> String {
+        match (self.min_time, self.max_time, self.types.is_empty()) {
+            (None, None, true) => String::new(),
+            _ => format!("~{}", serde_json::to_string(self).expect("filter")),
+        }
+    }
+
+    /// Whether the given transaction, with its `type` and `time` in seconds,
+    /// matches this filter.
+    pub(crate) fn matches_transaction(&self, txn: &Value) -> bool {
+        let type_matches = self.types.is_empty()
+            || matches!(str_field(txn, "type"), Some(t) if self.types.iter().any(|s| s == t));
+        type_matches && self.matches_time(u64_field(txn, "time").map(|time| time as i64))
+    }
+
+    /// Whether the given reward, with its ISO 8601 `timestamp`, matches this
+    /// filter.
+    pub(crate) fn matches_reward(&self, reward: &Value) -> bool {
+        self.matches_time(reward_time(reward))
+    }
+
+    fn matches_time(&self, time: Option<i64>) -> bool {
+        match time {
+            Some(time) => {
+                !matches!(self.min_time, Some(min) if time < min)
+                    && !matches!(self.max_time, Some(max) if time >= max)
+            }
+            None => self.min_time.is_none() && self.max_time.is_none(),
+        }
+    }
+}
+
+/// The time of a reward in seconds since the epoch.
+pub(crate) fn reward_time(reward: &Value) -> Option<i64> {
+    str_field(reward, "timestamp")
+        .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+        .map(|time| time.timestamp())
+}
+
+/// Parse an ISO 8601 timestamp, or a time relative to now like `-3 hour`.
+fn parse_time(time: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(time) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let invalid = || format!("invalid time {}", time);
+    let (amount, unit) = time.trim().split_once(' ').ok_or_else(invalid)?;
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let unit = match unit.trim().trim_end_matches('s') {
+        "second" => Duration::seconds(1),
+        "minute" => Duration::minutes(1),
+        "hour" => Duration::hours(1),
+        "day" => Duration::days(1),
+        "week" => Duration::weeks(1),
+        _ => return Err(invalid()),
+    };
+    Ok(now + unit * amount as i32)
+}
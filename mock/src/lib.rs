@@ -0,0 +1,482 @@
+//! A mock blockchain-http server for testing Helium API clients.
+//!
+//! The server serves `MockData` under `/v1` using the same `{data, cursor}`
+//! envelopes and cursor paging as the live API. A `MockConfig` controls the
+//! page size and injects latency, rate limiting, server errors and empty
+//! pages, which makes it possible to exercise client retry and paging
+//! behavior without network access.
+//!
+//! ```no_run
+//! use helium_api_mock::{MockConfig, MockData, MockServer};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> std::io::Result<()> {
+//! let data = MockData::load("mock.json")?;
+//! let config = MockConfig::default().page_size(10).server_error_every(5);
+//! let server = MockServer::start(data, config).await?;
+//! println!("serving {}", server.base_url());
+//! # Ok(())
+//! # }
+//! ```
+use axum::{
+    extract::{Request, State as AxumState},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use tokio::{net::TcpListener, sync::oneshot};
+
+mod data;
+mod filter;
+mod routes;
+
+pub use data::MockData;
+
+/// The default number of entries in a page.
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Controls paging and the faults injected by a mock server.
+#[derive(Clone, Debug)]
+pub struct MockConfig {
+    page_size: usize,
+    latency: Duration,
+    rate_limit_every: Option<u64>,
+    retry_after: Option<u64>,
+    server_error_every: Option<u64>,
+    empty_pages: usize,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            page_size: DEFAULT_PAGE_SIZE,
+            latency: Duration::ZERO,
+            rate_limit_every: None,
+            retry_after: None,
+            server_error_every: None,
+            empty_pages: 0,
+        }
+    }
+}
+
+impl MockConfig {
+    /// Set the maximum number of entries in a page.
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Delay every response by the given duration.
+    pub fn latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Answer every nth request with a 429 Too Many Requests response.
+    pub fn rate_limit_every(mut self, n: u64) -> Self {
+        self.rate_limit_every = Some(n);
+        self
+    }
+
+    /// Set the number of seconds sent in the `Retry-After` header of rate
+    /// limited responses. No header is sent by default.
+    pub fn retry_after(mut self, seconds: u64) -> Self {
+        self.retry_after = Some(seconds);
+        self
+    }
+
+    /// Answer every nth request with a 503 Service Unavailable response.
+    pub fn server_error_every(mut self, n: u64) -> Self {
+        self.server_error_every = Some(n);
+        self
+    }
+
+    /// Serve the given number of empty pages, each with a cursor, before
+    /// every page after the first. The live API does this when a time range
+    /// query scans blocks without matching entries.
+    pub fn empty_pages(mut self, empty_pages: usize) -> Self {
+        self.empty_pages = empty_pages;
+        self
+    }
+}
+
+pub(crate) struct State {
+    pub(crate) data: MockData,
+    pub(crate) config: MockConfig,
+    pub(crate) pending: Mutex<Vec<Value>>,
+    requests: AtomicU64,
+}
+
+/// Build the router for a mock server with the given data and configuration.
+fn router(state: Arc<State>) -> Router {
+    Router::new()
+        .nest("/v1", routes::router())
+        .layer(middleware::from_fn_with_state(state.clone(), faults))
+        .with_state(state)
+}
+
+async fn faults(AxumState(state): AxumState<Arc<State>>, request: Request, next: Next) -> Response {
+    let n = state.requests.fetch_add(1, Ordering::SeqCst) + 1;
+    let config = &state.config;
+    if !config.latency.is_zero() {
+        tokio::time::sleep(config.latency).await;
+    }
+    let every = |every: Option<u64>| matches!(every, Some(every) if every > 0 && n % every == 0);
+    if every(config.rate_limit_every) {
+        let mut response = (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({"error": "Too Many Requests"})),
+        )
+            .into_response();
+        if let Some(seconds) = config.retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, seconds.into());
+        }
+        return response;
+    }
+    if every(config.server_error_every) {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": "Service Unavailable"})),
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
+/// Serve the given data on the given listener until the process exits.
+pub async fn serve(listener: TcpListener, data: MockData, config: MockConfig) -> io::Result<()> {
+    axum::serve(listener, router(State::new(data, config))).await
+}
+
+impl State {
+    fn new(data: MockData, config: MockConfig) -> Arc<Self> {
+        Arc::new(Self {
+            data,
+            config,
+            pending: Mutex::new(Vec::new()),
+            requests: AtomicU64::new(0),
+        })
+    }
+}
+
+/// A mock server running in the background. The server shuts down when
+/// this value is dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<State>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Start a server on a free local port.
+    pub async fn start(data: MockData, config: MockConfig) -> io::Result<Self> {
+        Self::bind(SocketAddr::from(([127, 0, 0, 1], 0)), data, config).await
+    }
+
+    /// Start a server on the given address.
+    pub async fn bind(addr: SocketAddr, data: MockData, config: MockConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let state = State::new(data, config);
+        let (shutdown, stopped) = oneshot::channel();
+        let app = router(state.clone());
+        tokio::spawn(async move {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = stopped.await;
+                })
+                .await
+        });
+        Ok(Self {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// The address the server listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The base URL to configure a client with.
+    pub fn base_url(&self) -> String {
+        format!("http://{}/v1", self.addr)
+    }
+
+    /// The number of requests received so far, including failed ones.
+    pub fn request_count(&self) -> u64 {
+        self.state.requests.load(Ordering::SeqCst)
+    }
+
+    /// The transactions submitted to the server so far.
+    pub fn pending_transactions(&self) -> Vec<Value> {
+        self.state
+            .pending
+            .lock()
+            .expect("pending transactions")
+            .clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::TryStreamExt;
+    use helium_api::{
+        accounts, blocks, hotspots,
+        models::{
+            transactions::{TransactionInfo, TransactionType},
+            Bucket, Hnt, QueryActivity, QueryTimeRange, RewardSums,
+        },
+        ouis, pending_transactions, vars, Client, RetryPolicy,
+    };
+    use tokio::test;
+
+    fn data() -> MockData {
+        let ouis = (1..=5)
+            .map(|oui| {
+                json!({
+                    "oui": oui,
+                    "owner": "owner",
+                    "nonce": 1,
+                    "addresses": [],
+                    "subnets": [{"base": oui * 8, "mask": 16777208}],
+                })
+            })
+            .collect();
+        let mut vars = serde_json::Map::new();
+        vars.insert("poc_version".to_string(), json!(11));
+        vars.insert("sc_version".to_string(), json!(2));
+        MockData {
+            ouis,
+            vars,
+            height: Some(1_000),
+            ..Default::default()
+        }
+    }
+
+    fn client(server: &MockServer) -> Client {
+        Client::builder()
+            .base_url(server.base_url())
+            .retry_policy(
+                RetryPolicy::default()
+                    .initial_backoff(Duration::from_millis(1))
+                    .jitter(false),
+            )
+            .build()
+            .expect("client")
+    }
+
+    #[test]
+    async fn paging() {
+        let server = MockServer::start(data(), MockConfig::default().page_size(2))
+            .await
+            .expect("server");
+        let client = client(&server);
+        let ouis: Vec<_> = ouis::all(&client).try_collect().await.expect("ouis");
+        assert_eq!(
+            ouis.iter().map(|o| o.oui).collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5]
+        );
+        assert_eq!(server.request_count(), 3);
+    }
+
+    #[test]
+    async fn empty_pages() {
+        let config = MockConfig::default().page_size(2).empty_pages(2);
+        let server = MockServer::start(data(), config).await.expect("server");
+        let client = client(&server);
+        let ouis: Vec<_> = ouis::all(&client).try_collect().await.expect("ouis");
+        assert_eq!(ouis.len(), 5);
+        assert_eq!(server.request_count(), 7);
+    }
+
+    #[test]
+    async fn faults() {
+        let config = MockConfig::default()
+            .page_size(2)
+            .rate_limit_every(2)
+            .retry_after(0)
+            .server_error_every(3);
+        let server = MockServer::start(data(), config).await.expect("server");
+        let client = client(&server);
+        let ouis: Vec<_> = ouis::all(&client).try_collect().await.expect("ouis");
+        assert_eq!(ouis.len(), 5);
+        assert_eq!(blocks::height(&client).await.expect("height"), 1_000);
+
+        let client = Client::builder()
+            .base_url(server.base_url())
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .expect("client");
+        let mut failures = 0;
+        for _ in 0..6 {
+            if let Err(err) = vars::get(&client).await {
+                assert!(err.is_retryable());
+                failures += 1;
+            }
+        }
+        assert_eq!(failures, 4);
+    }
+
+    #[test]
+    async fn submit() {
+        let server = MockServer::start(data(), MockConfig::default())
+            .await
+            .expect("server");
+        let client = client(&server);
+        let submitted = pending_transactions::submit(&client, b"txn")
+            .await
+            .expect("submit");
+        let status = pending_transactions::get(&client, &submitted.hash)
+            .await
+            .expect("status");
+        assert_eq!(status.hash, submitted.hash);
+        assert_eq!(server.pending_transactions().len(), 1);
+        let err = pending_transactions::get(&client, "unknown")
+            .await
+            .unwrap_err();
+        assert!(err.is_not_found());
+    }
+
+    /// Payments to and coinbases for an account every hour, and hotspot
+    /// rewards every 12 hours from 2022-06-01.
+    fn activity_data() -> MockData {
+        const START: u64 = 1_654_041_600;
+        let transactions = (0..6)
+            .map(|n| match n % 2 {
+                0 => json!({
+                    "type": "payment_v1",
+                    "hash": format!("payment{}", n),
+                    "height": 1_000 + n,
+                    "time": START + n * 3_600,
+                    "amount": 100,
+                    "fee": 0,
+                    "nonce": n,
+                    "payer": "payer",
+                    "payee": "account",
+                }),
+                _ => json!({
+                    "type": "dc_coinbase_v1",
+                    "hash": format!("coinbase{}", n),
+                    "height": 1_000 + n,
+                    "time": START + n * 3_600,
+                    "amount": 100,
+                    "payee": "account",
+                }),
+            })
+            .collect();
+        let rewards = (0..4)
+            .map(|n| {
+                json!({
+                    "account": "account",
+                    "gateway": "hotspot",
+                    "amount": (n + 1) * 100_000_000,
+                    "block": 1_000 + n,
+                    "hash": format!("reward{}", n),
+                    "timestamp": format!("2022-06-0{}T{:02}:00:00Z", 1 + n / 2, n % 2 * 12),
+                })
+            })
+            .collect();
+        MockData {
+            transactions,
+            rewards,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    async fn activity_filters() {
+        let config = MockConfig::default().page_size(1).empty_pages(1);
+        let server = MockServer::start(activity_data(), config)
+            .await
+            .expect("server");
+        let client = client(&server);
+        let query = QueryActivity {
+            min_time: Some("2022-06-01T01:00:00Z".into()),
+            max_time: Some("2022-06-01T05:00:00Z".into()),
+            filter_types: vec![TransactionType::PaymentV1],
+        };
+        let activity: Vec<_> = hotspots::activity(&client, "account", &query)
+            .try_collect()
+            .await
+            .expect("activity");
+        let hashes: Vec<_> = activity.iter().map(|txn| txn.hash()).collect();
+        assert_eq!(hashes, ["payment2", "payment4"]);
+
+        let query = QueryActivity {
+            min_time: Some("soon".into()),
+            ..Default::default()
+        };
+        let err = accounts::activity(&client, "account", &query)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().map(|s| s.as_u16()), Some(400));
+    }
+
+    #[test]
+    async fn rewards_filters() {
+        let config = MockConfig::default().page_size(1);
+        let server = MockServer::start(activity_data(), config)
+            .await
+            .expect("server");
+        let client = client(&server);
+        let query = QueryTimeRange {
+            min_time: "2022-06-01T12:00:00Z".into(),
+            max_time: "2022-06-02T12:00:00Z".into(),
+        };
+        let rewards: Vec<_> = accounts::rewards(&client, "account", &query)
+            .try_collect()
+            .await
+            .expect("rewards");
+        let hashes: Vec<_> = rewards.iter().map(|reward| reward.hash.as_str()).collect();
+        assert_eq!(hashes, ["reward1", "reward2"]);
+
+        let sum = hotspots::rewards_sum(&client, "hotspot", &query, None)
+            .await
+            .expect("sum");
+        if let RewardSums::Total(total) = sum {
+            assert_eq!(total.sum, Hnt::from(500_000_000));
+            assert_eq!(total.max, Hnt::from(300_000_000));
+        } else {
+            panic!()
+        }
+
+        let query = QueryTimeRange {
+            min_time: "2022-06-01T00:00:00Z".into(),
+            max_time: "2022-06-03T00:00:00Z".into(),
+        };
+        let sums = hotspots::rewards_sum(&client, "hotspot", &query, Some(Bucket::Day))
+            .await
+            .expect("buckets");
+        if let RewardSums::Buckets(buckets) = sums {
+            let totals: Vec<_> = buckets.iter().map(|bucket| bucket.rewards.sum).collect();
+            assert_eq!(totals, [Hnt::from(700_000_000), Hnt::from(300_000_000)]);
+        } else {
+            panic!()
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use clap::Parser;
+use helium_api_mock::{serve, MockConfig, MockData, DEFAULT_PAGE_SIZE};
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
+use tokio::net::TcpListener;
+
+/// Serve mock blockchain-http data for testing Helium API clients.
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Cli {
+    /// JSON file with the data to serve
+    #[arg(long)]
+    data: Option<PathBuf>,
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:3000")]
+    listen: SocketAddr,
+    /// Maximum number of entries in a page
+    #[arg(long, default_value_t = DEFAULT_PAGE_SIZE)]
+    page_size: usize,
+    /// Delay every response by the given number of milliseconds
+    #[arg(long, default_value_t = 0)]
+    latency_ms: u64,
+    /// Answer every nth request with a 429 response
+    #[arg(long)]
+    rate_limit_every: Option<u64>,
+    /// Seconds to send in the Retry-After header of 429 responses
+    #[arg(long)]
+    retry_after: Option<u64>,
+    /// Answer every nth request with a 503 response
+    #[arg(long)]
+    server_error_every: Option<u64>,
+    /// Number of empty pages served before every page after the first
+    #[arg(long, default_value_t = 0)]
+    empty_pages: usize,
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let cli = Cli::parse();
+    let data = match &cli.data {
+        Some(path) => MockData::load(path)?,
+        None => MockData::default(),
+    };
+    let mut config = MockConfig::default()
+        .page_size(cli.page_size)
+        .latency(Duration::from_millis(cli.latency_ms))
+        .empty_pages(cli.empty_pages);
+    if let Some(n) = cli.rate_limit_every {
+        config = config.rate_limit_every(n);
+    }
+    if let Some(seconds) = cli.retry_after {
+        config = config.retry_after(seconds);
+    }
+    if let Some(n) = cli.server_error_every {
+        config = config.server_error_every(n);
+    }
+    let listener = TcpListener::bind(cli.listen).await?;
+    println!("serving http://{}/v1", listener.local_addr()?);
+    serve(listener, data, config).await
+}
@@ -0,0 +1,583 @@
+use crate::{
+    data::{involves, str_field, u64_field},
+    filter::{reward_time, Filter, FilterQuery},
+    State,
+};
+use axum::{
+    extract::{Path, Query, State as AxumState},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+type AppState = AxumState<Arc<State>>;
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct PageQuery {
+    cursor: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct LimitQuery {
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct KeysQuery {
+    keys: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct SumQuery {
+    #[serde(flatten)]
+    filter: FilterQuery,
+    bucket: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Submission {
+    txn: String,
+}
+
+pub(crate) fn router() -> Router<Arc<State>> {
+    Router::new()
+        .route("/accounts", get(accounts))
+        .route("/accounts/rich", get(accounts_rich))
+        .route("/accounts/{address}", get(account))
+        .route("/accounts/{address}/hotspots", get(account_hotspots))
+        .route("/accounts/{address}/ouis", get(account_ouis))
+        .route("/accounts/{address}/validators", get(account_validators))
+        .route("/accounts/{address}/activity", get(account_activity))
+        .route("/accounts/{address}/rewards", get(account_rewards))
+        .route("/accounts/{address}/rewards/sum", get(account_rewards_sum))
+        .route("/hotspots", get(hotspots))
+        .route("/hotspots/{address}", get(hotspot))
+        .route("/hotspots/{address}/activity", get(hotspot_activity))
+        .route("/hotspots/{address}/rewards", get(hotspot_rewards))
+        .route("/hotspots/{address}/rewards/sum", get(hotspot_rewards_sum))
+        .route("/blocks", get(blocks))
+        .route("/blocks/height", get(height))
+        .route("/blocks/{height}/transactions", get(block_transactions))
+        .route(
+            "/blocks/hash/{hash}/transactions",
+            get(block_hash_transactions),
+        )
+        .route("/oracle/prices", get(oracle_prices))
+        .route("/oracle/prices/current", get(oracle_price_current))
+        .route("/oracle/prices/{block}", get(oracle_price_at_block))
+        .route("/oracle/predictions", get(oracle_predictions))
+        .route("/ouis", get(ouis))
+        .route("/ouis/last", get(oui_last))
+        .route("/ouis/stats", get(oui_stats))
+        .route("/ouis/{oui}", get(oui))
+        .route("/validators", get(validators))
+        .route("/validators/stats", get(validator_stats))
+        .route("/validators/{address}", get(validator))
+        .route("/validators/{address}/rewards", get(validator_rewards))
+        .route(
+            "/validators/{address}/rewards/sum",
+            get(validator_rewards_sum),
+        )
+        .route("/vars", get(vars))
+        .route("/transactions/{hash}", get(transaction))
+        .route("/pending_transactions", post(submit_transaction))
+        .route("/pending_transactions/{hash}", get(pending_transaction))
+}
+
+fn data(value: Value) -> Response {
+    Json(json!({ "data": value })).into_response()
+}
+
+fn found(value: Option<&Value>) -> Response {
+    match value {
+        Some(value) => data(value.clone()),
+        None => not_found(),
+    }
+}
+
+fn not_found() -> Response {
+    (StatusCode::NOT_FOUND, Json(json!({"error": "Not Found"}))).into_response()
+}
+
+fn bad_request(error: &str) -> Response {
+    (StatusCode::BAD_REQUEST, Json(json!({ "error": error }))).into_response()
+}
+
+/// Serve a page of the given entries for the given cursor. Cursors are the
+/// offset of the next entry, optionally followed by the number of empty pages
+/// already served for that offset.
+fn page<'a, I>(state: &State, entries: I, query: &PageQuery) -> Response
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    page_with_suffix(state, entries, query.cursor.as_deref(), "")
+}
+
+/// Serve a page of the given entries like `page`, appending the given suffix
+/// to the cursor of the next page.
+fn page_with_suffix<'a, I>(
+    state: &State,
+    entries: I,
+    cursor: Option<&str>,
+    suffix: &str,
+) -> Response
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    let entries: Vec<&Value> = entries.into_iter().collect();
+    let (offset, empty_pages) = match cursor.map(parse_cursor) {
+        Some(Some(cursor)) => cursor,
+        Some(None) => return bad_request("invalid cursor"),
+        None => (0, 0),
+    };
+    if offset > 0 && empty_pages < state.config.empty_pages {
+        return Json(json!({
+            "data": [],
+            "cursor": format!("{}.{}{}", offset, empty_pages + 1, suffix),
+        }))
+        .into_response();
+    }
+    let end = entries
+        .len()
+        .min(offset.saturating_add(state.config.page_size.max(1)));
+    let items: Vec<&Value> = entries.get(offset..end).unwrap_or_default().to_vec();
+    if end < entries.len() {
+        Json(json!({"data": items, "cursor": format!("{}{}", end, suffix)})).into_response()
+    } else {
+        Json(json!({ "data": items })).into_response()
+    }
+}
+
+/// Serve a page of the given entries that match the filter of the given
+/// query, keeping the filter in the cursor of the next page.
+fn filtered_page<'a, I, F>(state: &State, entries: I, query: &FilterQuery, matches: F) -> Response
+where
+    I: IntoIterator<Item = &'a Value>,
+    F: Fn(&Filter, &Value) -> bool,
+{
+    let (filter, cursor) = match Filter::resolve(query) {
+        Ok(resolved) => resolved,
+        Err(error) => return bad_request(&error),
+    };
+    let entries = entries.into_iter().filter(|entry| matches(&filter, entry));
+    page_with_suffix(state, entries, cursor.as_deref(), &filter.cursor_suffix())
+}
+
+/// Serve the sum of the given rewards that match the time range of the
+/// given query, optionally split into buckets.
+fn rewards_sum<'a, I>(rewards: I, query: &SumQuery) -> Response
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    let filter = match Filter::resolve(&query.filter) {
+        Ok((filter, _)) => filter,
+        Err(error) => return bad_request(&error),
+    };
+    let rewards: Vec<&Value> = rewards
+        .into_iter()
+        .filter(|reward| filter.matches_reward(reward))
+        .collect();
+    let bucket = match query.bucket.as_deref() {
+        None => return data(sum(&rewards)),
+        Some("hour") => 3_600,
+        Some("day") => 86_400,
+        Some("week") => 604_800,
+        Some(_) => return bad_request("invalid bucket"),
+    };
+    let (min_time, max_time) = match filter.time_range() {
+        Some(range) => range,
+        None => return bad_request("buckets need both min_time and max_time"),
+    };
+    let starts: Vec<i64> = (min_time..max_time).step_by(bucket as usize).collect();
+    let buckets = starts
+        .into_iter()
+        .rev()
+        .map(|start| {
+            let in_bucket: Vec<&Value> = rewards
+                .iter()
+                .copied()
+                .filter(|reward| {
+                    matches!(reward_time(reward), Some(time) if time >= start && time < start + bucket)
+                })
+                .collect();
+            let mut sum = sum(&in_bucket);
+            let timestamp = DateTime::<Utc>::from_timestamp(start, 0).expect("bucket time");
+            sum["timestamp"] = json!(timestamp.to_rfc3339_opts(SecondsFormat::Micros, true));
+            sum
+        })
+        .collect();
+    data(Value::Array(buckets))
+}
+
+/// The sum of the given rewards with statistics of their amounts in HNT.
+fn sum(rewards: &[&Value]) -> Value {
+    let mut amounts: Vec<u64> = rewards
+        .iter()
+        .filter_map(|reward| u64_field(reward, "amount"))
+        .collect();
+    amounts.sort_unstable();
+    let total: u64 = amounts.iter().sum();
+    let hnt = |bones: f64| bones / 100_000_000.0;
+    let count = amounts.len().max(1) as f64;
+    let avg = total as f64 / count;
+    let variance = amounts
+        .iter()
+        .map(|amount| (*amount as f64 - avg).powi(2))
+        .sum::<f64>()
+        / count;
+    let median = match amounts.len() {
+        0 => 0.0,
+        len if len % 2 == 0 => (amounts[len / 2 - 1] + amounts[len / 2]) as f64 / 2.0,
+        len => amounts[len / 2] as f64,
+    };
+    json!({
+        "sum": total,
+        "total": hnt(total as f64),
+        "min": hnt(amounts.first().copied().unwrap_or(0) as f64),
+        "max": hnt(amounts.last().copied().unwrap_or(0) as f64),
+        "median": hnt(median),
+        "avg": hnt(avg),
+        "stddev": hnt(variance.sqrt()),
+    })
+}
+
+fn parse_cursor(cursor: &str) -> Option<(usize, usize)> {
+    match cursor.split_once('.') {
+        Some((offset, empty_pages)) => Some((offset.parse().ok()?, empty_pages.parse().ok()?)),
+        None => Some((cursor.parse().ok()?, 0)),
+    }
+}
+
+fn by_str<'a>(entries: &'a [Value], field: &str, value: &str) -> Option<&'a Value> {
+    entries.iter().find(|e| str_field(e, field) == Some(value))
+}
+
+fn filter_str<'a>(
+    entries: &'a [Value],
+    field: &'a str,
+    value: &'a str,
+) -> impl Iterator<Item = &'a Value> {
+    entries
+        .iter()
+        .filter(move |e| str_field(e, field) == Some(value))
+}
+
+async fn accounts(AxumState(state): AppState, Query(query): Query<PageQuery>) -> Response {
+    page(&state, &state.data.accounts, &query)
+}
+
+async fn accounts_rich(AxumState(state): AppState, Query(query): Query<LimitQuery>) -> Response {
+    let mut accounts = state.data.accounts.clone();
+    accounts.sort_by_key(|a| std::cmp::Reverse(u64_field(a, "balance").unwrap_or(0)));
+    accounts.truncate(query.limit.unwrap_or(1000).min(1000));
+    data(Value::Array(accounts))
+}
+
+async fn account(AxumState(state): AppState, Path(address): Path<String>) -> Response {
+    found(by_str(&state.data.accounts, "address", &address))
+}
+
+async fn account_hotspots(
+    AxumState(state): AppState,
+    Path(address): Path<String>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    page(
+        &state,
+        filter_str(&state.data.hotspots, "owner", &address),
+        &query,
+    )
+}
+
+async fn account_ouis(
+    AxumState(state): AppState,
+    Path(address): Path<String>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    page(
+        &state,
+        filter_str(&state.data.ouis, "owner", &address),
+        &query,
+    )
+}
+
+async fn account_validators(
+    AxumState(state): AppState,
+    Path(address): Path<String>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    page(
+        &state,
+        filter_str(&state.data.validators, "owner", &address),
+        &query,
+    )
+}
+
+async fn account_activity(
+    AxumState(state): AppState,
+    Path(address): Path<String>,
+    Query(query): Query<FilterQuery>,
+) -> Response {
+    let activity = state
+        .data
+        .transactions
+        .iter()
+        .filter(|txn| involves(txn, &address));
+    filtered_page(&state, activity, &query, Filter::matches_transaction)
+}
+
+/// The hotspot and validator rewards earned by the given account.
+fn account_reward_entries<'a>(
+    state: &'a State,
+    address: &'a str,
+) -> impl Iterator<Item = &'a Value> {
+    state
+        .data
+        .rewards
+        .iter()
+        .chain(&state.data.validator_rewards)
+        .filter(move |reward| str_field(reward, "account") == Some(address))
+}
+
+async fn account_rewards(
+    AxumState(state): AppState,
+    Path(address): Path<String>,
+    Query(query): Query<FilterQuery>,
+) -> Response {
+    let rewards = account_reward_entries(&state, &address);
+    filtered_page(&state, rewards, &query, Filter::matches_reward)
+}
+
+async fn account_rewards_sum(
+    AxumState(state): AppState,
+    Path(address): Path<String>,
+    Query(query): Query<SumQuery>,
+) -> Response {
+    rewards_sum(account_reward_entries(&state, &address), &query)
+}
+
+async fn hotspots(AxumState(state): AppState, Query(query): Query<PageQuery>) -> Response {
+    page(&state, &state.data.hotspots, &query)
+}
+
+async fn hotspot(AxumState(state): AppState, Path(address): Path<String>) -> Response {
+    found(by_str(&state.data.hotspots, "address", &address))
+}
+
+async fn hotspot_activity(
+    AxumState(state): AppState,
+    Path(address): Path<String>,
+    Query(query): Query<FilterQuery>,
+) -> Response {
+    let activity = state
+        .data
+        .transactions
+        .iter()
+        .filter(|txn| involves(txn, &address));
+    filtered_page(&state, activity, &query, Filter::matches_transaction)
+}
+
+async fn hotspot_rewards(
+    AxumState(state): AppState,
+    Path(address): Path<String>,
+    Query(query): Query<FilterQuery>,
+) -> Response {
+    let rewards = filter_str(&state.data.rewards, "gateway", &address);
+    filtered_page(&state, rewards, &query, Filter::matches_reward)
+}
+
+async fn hotspot_rewards_sum(
+    AxumState(state): AppState,
+    Path(address): Path<String>,
+    Query(query): Query<SumQuery>,
+) -> Response {
+    rewards_sum(filter_str(&state.data.rewards, "gateway", &address), &query)
+}
+
+async fn blocks(AxumState(state): AppState, Query(query): Query<PageQuery>) -> Response {
+    page(&state, &state.data.blocks, &query)
+}
+
+async fn height(AxumState(state): AppState) -> Response {
+    data(json!({ "height": state.data.height() }))
+}
+
+async fn block_transactions(
+    AxumState(state): AppState,
+    Path(height): Path<u64>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let transactions = state
+        .data
+        .transactions
+        .iter()
+        .filter(|txn| u64_field(txn, "height") == Some(height));
+    page(&state, transactions, &query)
+}
+
+async fn block_hash_transactions(
+    AxumState(state): AppState,
+    Path(hash): Path<String>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let height =
+        match by_str(&state.data.blocks, "hash", &hash).and_then(|b| u64_field(b, "height")) {
+            Some(height) => height,
+            None => return not_found(),
+        };
+    let transactions = state
+        .data
+        .transactions
+        .iter()
+        .filter(|txn| u64_field(txn, "height") == Some(height));
+    page(&state, transactions, &query)
+}
+
+async fn oracle_prices(AxumState(state): AppState, Query(query): Query<PageQuery>) -> Response {
+    page(&state, &state.data.oracle_prices, &query)
+}
+
+async fn oracle_price_current(AxumState(state): AppState) -> Response {
+    found(state.data.oracle_prices.first())
+}
+
+async fn oracle_price_at_block(AxumState(state): AppState, Path(block): Path<u64>) -> Response {
+    found(
+        state
+            .data
+            .oracle_prices
+            .iter()
+            .find(|price| matches!(u64_field(price, "block"), Some(b) if b <= block)),
+    )
+}
+
+async fn oracle_predictions(AxumState(state): AppState) -> Response {
+    data(Value::Array(state.data.oracle_predictions.clone()))
+}
+
+async fn ouis(AxumState(state): AppState, Query(query): Query<PageQuery>) -> Response {
+    page(&state, &state.data.ouis, &query)
+}
+
+async fn oui(AxumState(state): AppState, Path(oui): Path<u64>) -> Response {
+    found(
+        state
+            .data
+            .ouis
+            .iter()
+            .find(|o| u64_field(o, "oui") == Some(oui)),
+    )
+}
+
+async fn oui_last(AxumState(state): AppState) -> Response {
+    found(
+        state
+            .data
+            .ouis
+            .iter()
+            .max_by_key(|o| u64_field(o, "oui").unwrap_or(0)),
+    )
+}
+
+async fn oui_stats(AxumState(state): AppState) -> Response {
+    data(json!({ "count": state.data.ouis.len() }))
+}
+
+async fn validators(AxumState(state): AppState, Query(query): Query<PageQuery>) -> Response {
+    page(&state, &state.data.validators, &query)
+}
+
+async fn validator(AxumState(state): AppState, Path(address): Path<String>) -> Response {
+    found(by_str(&state.data.validators, "address", &address))
+}
+
+async fn validator_stats(AxumState(state): AppState) -> Response {
+    let stats = |status: &str| {
+        let staked: Vec<&Value> =
+            filter_str(&state.data.validators, "stake_status", status).collect();
+        let amount: u64 = staked.iter().filter_map(|v| u64_field(v, "stake")).sum();
+        json!({
+            "amount": amount as f64 / 100_000_000.0,
+            "count": staked.len(),
+        })
+    };
+    data(json!({
+        "active": Value::Null,
+        "staked": stats("staked"),
+        "unstaked": stats("unstaked"),
+        "cooldown": stats("cooldown"),
+    }))
+}
+
+async fn validator_rewards(
+    AxumState(state): AppState,
+    Path(address): Path<String>,
+    Query(query): Query<FilterQuery>,
+) -> Response {
+    let rewards = filter_str(&state.data.validator_rewards, "gateway", &address);
+    filtered_page(&state, rewards, &query, Filter::matches_reward)
+}
+
+async fn validator_rewards_sum(
+    AxumState(state): AppState,
+    Path(address): Path<String>,
+    Query(query): Query<SumQuery>,
+) -> Response {
+    let rewards = filter_str(&state.data.validator_rewards, "gateway", &address);
+    rewards_sum(rewards, &query)
+}
+
+async fn vars(AxumState(state): AppState, Query(query): Query<KeysQuery>) -> Response {
+    let vars = match query.keys {
+        Some(keys) => keys
+            .split(',')
+            .filter_map(|key| {
+                state
+                    .data
+                    .vars
+                    .get(key)
+                    .map(|value| (key.to_string(), value.clone()))
+            })
+            .collect(),
+        None => state.data.vars.clone(),
+    };
+    data(Value::Object(vars))
+}
+
+async fn transaction(AxumState(state): AppState, Path(hash): Path<String>) -> Response {
+    found(by_str(&state.data.transactions, "hash", &hash))
+}
+
+async fn submit_transaction(
+    AxumState(state): AppState,
+    Json(submission): Json<Submission>,
+) -> Response {
+    let mut hasher = DefaultHasher::new();
+    submission.txn.hash(&mut hasher);
+    let hash = format!("{:016x}", hasher.finish());
+    let pending = json!({
+        "hash": hash,
+        "status": "pending",
+        "txn": submission.txn,
+    });
+    state
+        .pending
+        .lock()
+        .expect("pending transactions")
+        .push(pending);
+    data(json!({ "hash": hash }))
+}
+
+async fn pending_transaction(AxumState(state): AppState, Path(hash): Path<String>) -> Response {
+    let pending = state.pending.lock().expect("pending transactions");
+    found(by_str(&pending, "hash", &hash))
+}
@@ -1,5 +1,8 @@
 use crate::{
-    models::{transactions::Transaction, Account, Hotspot, Oui, QueryTimeRange, Validator},
+    models::{
+        transactions::TransactionEnvelope, Account, Bucket, Hotspot, Oui, QueryActivity,
+        QueryRewardSum, QueryTimeRange, Reward, RewardSums, Validator,
+    },
     *,
 };
 
@@ -8,6 +11,12 @@ pub fn all(client: &Client) -> Stream<Account> {
     client.fetch_stream("/accounts", NO_QUERY)
 }
 
+/// Get all known accounts as pages, starting after the page for the given
+/// cursor if any
+pub fn all_pages(client: &Client, cursor: Option<&str>) -> Stream<Page<Account>> {
+    client.fetch_pages("/accounts", NO_QUERY, cursor)
+}
+
 /// Get a specific account by its address
 pub async fn get(client: &Client, address: &str) -> Result<Account> {
     client
@@ -15,21 +24,69 @@ pub async fn get(client: &Client, address: &str) -> Result<Account> {
         .await
 }
 
+/// Get the accounts for the given addresses, with up to `concurrency` requests
+/// in flight, or one if `concurrency` is zero. Each result is returned with its
+/// address, in the order given by `order`, and a failed lookup does not end the
+/// stream.
+pub fn get_many<I, S>(
+    client: &Client,
+    addresses: I,
+    concurrency: usize,
+    order: Order,
+) -> Batch<Account>
+where
+    I: IntoIterator<Item = S>,
+    I::IntoIter: Send + 'static,
+    S: Into<String>,
+{
+    client.get_many("/accounts", addresses, concurrency, order)
+}
+
 /// Get all hotspots owned by a given account
 pub fn hotspots(client: &Client, address: &str) -> Stream<Hotspot> {
     client.fetch_stream(&format!("/accounts/{}/hotspots", address), NO_QUERY)
 }
 
+/// Get all hotspots owned by a given account as pages, starting after the
+/// page for the given cursor if any
+pub fn hotspots_pages(
+    client: &Client,
+    address: &str,
+    cursor: Option<&str>,
+) -> Stream<Page<Hotspot>> {
+    client.fetch_pages(&format!("/accounts/{}/hotspots", address), NO_QUERY, cursor)
+}
+
 /// Get all OUIs owned by a given account
 pub fn ouis(client: &Client, address: &str) -> Stream<Oui> {
     client.fetch_stream(&format!("/accounts/{}/ouis", address), NO_QUERY)
 }
 
+/// Get all OUIs owned by a given account as pages, starting after the page
+/// for the given cursor if any
+pub fn ouis_pages(client: &Client, address: &str, cursor: Option<&str>) -> Stream<Page<Oui>> {
+    client.fetch_pages(&format!("/accounts/{}/ouis", address), NO_QUERY, cursor)
+}
+
 /// Get all validators owned by a given account
 pub fn validators(client: &Client, address: &str) -> Stream<Validator> {
     client.fetch_stream(&format!("/accounts/{}/validators", address), NO_QUERY)
 }
 
+/// Get all validators owned by a given account as pages, starting after the
+/// page for the given cursor if any
+pub fn validators_pages(
+    client: &Client,
+    address: &str,
+    cursor: Option<&str>,
+) -> Stream<Page<Validator>> {
+    client.fetch_pages(
+        &format!("/accounts/{}/validators", address),
+        NO_QUERY,
+        cursor,
+    )
+}
+
 /// Get a list of of up to a limit (maximum 1000) accounts sorted by their balance in
 /// descending order
 pub async fn richest(client: &Client, limit: Option<u32>) -> Result<Vec<Account>> {
@@ -40,13 +97,67 @@ pub async fn richest(client: &Client, limit: Option<u32>) -> Result<Vec<Account>
 
 /// Fetches transactions that indicate activity for an account. This includes any
 /// transaction that involves the account, usually as a payer, payee or owner.
-pub fn activity(client: &Client, address: &str, query: &QueryTimeRange) -> Stream<Transaction> {
+/// Only transactions of the types in `filter_types` are returned, if any.
+pub fn activity(
+    client: &Client,
+    address: &str,
+    query: &QueryActivity,
+) -> Stream<TransactionEnvelope> {
     client.fetch_stream(&format!("/accounts/{}/activity", address), query)
 }
 
+/// Fetches account activity as pages, starting after the page for the given
+/// cursor if any. The query is ignored when resuming from a cursor.
+pub fn activity_pages(
+    client: &Client,
+    address: &str,
+    query: &QueryActivity,
+    cursor: Option<&str>,
+) -> Stream<Page<TransactionEnvelope>> {
+    client.fetch_pages(&format!("/accounts/{}/activity", address), query, cursor)
+}
+
+/// Get rewards for an account
+///
+/// Returns the rewards earned by the given account per reward block, for a
+/// given timeframe. The block that contains the max_time timestamp is
+/// excluded from the result.
+pub fn rewards(client: &Client, address: &str, query: &QueryTimeRange) -> Stream<Reward> {
+    client.fetch_stream(&format!("/accounts/{}/rewards", address), query)
+}
+
+/// Get rewards for an account as pages, starting after the page for the
+/// given cursor if any. The query is ignored when resuming from a cursor.
+pub fn rewards_pages(
+    client: &Client,
+    address: &str,
+    query: &QueryTimeRange,
+    cursor: Option<&str>,
+) -> Stream<Page<Reward>> {
+    client.fetch_pages(&format!("/accounts/{}/rewards", address), query, cursor)
+}
+
+/// Get the sum of the rewards for an account in the given timeframe, or the
+/// sums for each bucket of the given size when a bucket is given
+pub async fn rewards_sum(
+    client: &Client,
+    address: &str,
+    query: &QueryTimeRange,
+    bucket: Option<Bucket>,
+) -> Result<RewardSums> {
+    let query = QueryRewardSum {
+        range: query,
+        bucket,
+    };
+    client
+        .fetch(&format!("/accounts/{}/rewards/sum", address), &query)
+        .await
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use models::Hnt;
     use tokio::test;
 
     #[test]
@@ -60,6 +171,26 @@ mod test {
         assert_eq!(accounts.len(), 10);
     }
 
+    #[test]
+    async fn all_pages() {
+        let client = get_test_client();
+        let pages = accounts::all_pages(&client, None)
+            .take(2)
+            .into_vec()
+            .await
+            .expect("pages");
+        let cursor = pages[0].cursor.as_deref().expect("cursor");
+        let resumed = accounts::all_pages(&client, Some(cursor))
+            .take(1)
+            .into_vec()
+            .await
+            .expect("resumed pages");
+        assert_eq!(
+            resumed[0].items.first().map(|account| &account.address),
+            pages[1].items.first().map(|account| &account.address)
+        );
+    }
+
     #[test]
     async fn get() {
         let client = get_test_client();
@@ -98,7 +229,7 @@ mod test {
         .into_vec()
         .await
         .expect("hotspot list");
-        assert!(hotspots.len() > 0);
+        assert!(!hotspots.is_empty());
     }
 
     #[test]
@@ -109,4 +240,26 @@ mod test {
             .expect("richest list");
         assert_eq!(richest.len(), 10);
     }
+
+    #[test]
+    async fn rewards_sum() {
+        let client = get_test_client();
+        let query = QueryTimeRange {
+            min_time: "2022-06-01T00:00:00Z".into(),
+            max_time: "2022-06-02T00:00:00Z".into(),
+        };
+        let sum = accounts::rewards_sum(
+            &client,
+            "13WRNw4fmssJBvMqMnREwe1eCvUVXfnWXSXGcWXyVvAnQUF3D9R",
+            &query,
+            None,
+        )
+        .await
+        .expect("rewards sum");
+        if let RewardSums::Total(total) = sum {
+            assert_eq!(total.sum, Hnt::from(124427463));
+        } else {
+            panic!()
+        }
+    }
 }
@@ -0,0 +1,772 @@
+//! A blocking API for synchronous programs.
+//!
+//! The `blocking::Client` wraps an async `Client` together with a single
+//! threaded tokio runtime that drives its requests. The modules in here mirror
+//! the async modules of the crate, returning values directly instead of
+//! futures, and iterators instead of streams for paged endpoints.
+//!
+//! The blocking client must not be used from within an async runtime, since
+//! blocking that runtime's thread on a request panics.
+//!
+//! ```no_run
+//! use helium_api::blocking::{accounts, blocks, Client};
+//!
+//! # fn main() -> helium_api::Result {
+//! let client = Client::default();
+//! let height = blocks::height(&client)?;
+//! for hotspot in accounts::hotspots(&client, "13buBykFQf5VaQtv7mWj2PBY9Lq4i1DeXhg7C4Vbu3ppzqqNkTH") {
+//!     println!("{}: {:?}", height, hotspot?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use crate::{ClientBuilder, Result, Stream};
+use futures::{Future as StdFuture, StreamExt};
+use std::sync::Arc;
+use tokio::runtime::{Builder, Runtime};
+
+/// A client that issues requests synchronously.
+///
+/// The client is cheap to clone and all clones share the same async client
+/// and runtime.
+#[derive(Clone, Debug)]
+pub struct Client {
+    client: crate::Client,
+    runtime: Arc<Runtime>,
+}
+
+impl Default for Client {
+    /// Create a blocking client for the `DEFAULT_BASE_URL` using the default
+    /// settings of a `ClientBuilder`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying HTTP client or runtime can not be
+    /// initialized. Use `ClientBuilder::build_blocking` to handle that error
+    /// instead.
+    fn default() -> Self {
+        ClientBuilder::default()
+            .build_blocking()
+            .expect("default blocking client configuration")
+    }
+}
+
+impl Client {
+    /// Create a blocking client that issues requests through the given async
+    /// client.
+    pub fn new(client: crate::Client) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self {
+            client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// The async client used for requests.
+    pub fn client(&self) -> &crate::Client {
+        &self.client
+    }
+
+    /// Get the `data` of an API path as JSON. See `crate::Client::get_json`.
+    pub fn get_json<Q>(&self, path: &str, query: &Q) -> Result<serde_json::Value>
+    where
+        Q: serde::Serialize + ?Sized,
+    {
+        self.block_on(self.client.get_json(path, query))
+    }
+
+    /// Get the entries of all pages of a paged API path as JSON.
+    pub fn stream_json<Q>(&self, path: &str, query: &Q) -> Iter<serde_json::Value>
+    where
+        Q: serde::Serialize + ?Sized,
+    {
+        self.iter(self.client.stream_json(path, query))
+    }
+
+    fn block_on<F: StdFuture>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    fn iter<T>(&self, stream: Stream<T>) -> Iter<T> {
+        Iter {
+            stream,
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+/// An iterator over the entries of a paged endpoint. Pages are fetched as
+/// the iterator advances.
+pub struct Iter<T> {
+    stream: Stream<T>,
+    runtime: Arc<Runtime>,
+}
+
+impl<T> Iterator for Iter<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl<T> std::fmt::Debug for Iter<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Iter").finish()
+    }
+}
+
+pub mod accounts {
+    use super::{Client, Iter};
+    use crate::{
+        models::{
+            transactions::TransactionEnvelope, Account, Bucket, Hotspot, Oui, QueryActivity,
+            QueryTimeRange, Reward, RewardSums, Validator,
+        },
+        Page, Result,
+    };
+
+    /// Get all known accounts
+    pub fn all(client: &Client) -> Iter<Account> {
+        client.iter(crate::accounts::all(&client.client))
+    }
+
+    /// Get all known accounts as pages, starting after the page for the given
+    /// cursor if any
+    pub fn all_pages(client: &Client, cursor: Option<&str>) -> Iter<Page<Account>> {
+        client.iter(crate::accounts::all_pages(&client.client, cursor))
+    }
+
+    /// Get a specific account by its address
+    pub fn get(client: &Client, address: &str) -> Result<Account> {
+        client.block_on(crate::accounts::get(&client.client, address))
+    }
+
+    /// Get all hotspots owned by a given account
+    pub fn hotspots(client: &Client, address: &str) -> Iter<Hotspot> {
+        client.iter(crate::accounts::hotspots(&client.client, address))
+    }
+
+    /// Get all hotspots owned by a given account as pages, starting after the
+    /// page for the given cursor if any
+    pub fn hotspots_pages(
+        client: &Client,
+        address: &str,
+        cursor: Option<&str>,
+    ) -> Iter<Page<Hotspot>> {
+        client.iter(crate::accounts::hotspots_pages(
+            &client.client,
+            address,
+            cursor,
+        ))
+    }
+
+    /// Get all OUIs owned by a given account
+    pub fn ouis(client: &Client, address: &str) -> Iter<Oui> {
+        client.iter(crate::accounts::ouis(&client.client, address))
+    }
+
+    /// Get all OUIs owned by a given account as pages, starting after the page
+    /// for the given cursor if any
+    pub fn ouis_pages(client: &Client, address: &str, cursor: Option<&str>) -> Iter<Page<Oui>> {
+        client.iter(crate::accounts::ouis_pages(&client.client, address, cursor))
+    }
+
+    /// Get all validators owned by a given account
+    pub fn validators(client: &Client, address: &str) -> Iter<Validator> {
+        client.iter(crate::accounts::validators(&client.client, address))
+    }
+
+    /// Get all validators owned by a given account as pages, starting after the
+    /// page for the given cursor if any
+    pub fn validators_pages(
+        client: &Client,
+        address: &str,
+        cursor: Option<&str>,
+    ) -> Iter<Page<Validator>> {
+        client.iter(crate::accounts::validators_pages(
+            &client.client,
+            address,
+            cursor,
+        ))
+    }
+
+    /// Fetches the richest accounts, up to the given limit or 1000
+    pub fn richest(client: &Client, limit: Option<u32>) -> Result<Vec<Account>> {
+        client.block_on(crate::accounts::richest(&client.client, limit))
+    }
+
+    /// Fetches the activity of a given account
+    pub fn activity(
+        client: &Client,
+        address: &str,
+        query: &QueryActivity,
+    ) -> Iter<TransactionEnvelope> {
+        client.iter(crate::accounts::activity(&client.client, address, query))
+    }
+
+    /// Fetches the activity of a given account as pages, starting after the
+    /// page for the given cursor if any
+    pub fn activity_pages(
+        client: &Client,
+        address: &str,
+        query: &QueryActivity,
+        cursor: Option<&str>,
+    ) -> Iter<Page<TransactionEnvelope>> {
+        client.iter(crate::accounts::activity_pages(
+            &client.client,
+            address,
+            query,
+            cursor,
+        ))
+    }
+
+    /// Get rewards for an account
+    pub fn rewards(client: &Client, address: &str, query: &QueryTimeRange) -> Iter<Reward> {
+        client.iter(crate::accounts::rewards(&client.client, address, query))
+    }
+
+    /// Get rewards for an account as pages, starting after the page for the
+    /// given cursor if any
+    pub fn rewards_pages(
+        client: &Client,
+        address: &str,
+        query: &QueryTimeRange,
+        cursor: Option<&str>,
+    ) -> Iter<Page<Reward>> {
+        client.iter(crate::accounts::rewards_pages(
+            &client.client,
+            address,
+            query,
+            cursor,
+        ))
+    }
+
+    /// Get the sum of the rewards for an account in the given timeframe, or
+    /// the sums for each bucket of the given size when a bucket is given
+    pub fn rewards_sum(
+        client: &Client,
+        address: &str,
+        query: &QueryTimeRange,
+        bucket: Option<Bucket>,
+    ) -> Result<RewardSums> {
+        client.block_on(crate::accounts::rewards_sum(
+            &client.client,
+            address,
+            query,
+            bucket,
+        ))
+    }
+}
+
+pub mod hotspots {
+    use super::{Client, Iter};
+    use crate::{
+        models::{
+            transactions::TransactionEnvelope, Bucket, Hotspot, QueryActivity, QueryTimeRange,
+            Reward, RewardSums, Witness,
+        },
+        Page, Result,
+    };
+
+    /// Get all known hotspots
+    pub fn all(client: &Client) -> Iter<Hotspot> {
+        client.iter(crate::hotspots::all(&client.client))
+    }
+
+    /// Get all known hotspots as pages, starting after the page for the given
+    /// cursor if any
+    pub fn all_pages(client: &Client, cursor: Option<&str>) -> Iter<Page<Hotspot>> {
+        client.iter(crate::hotspots::all_pages(&client.client, cursor))
+    }
+
+    /// Get a specific hotspot by its address
+    pub fn get(client: &Client, address: &str) -> Result<Hotspot> {
+        client.block_on(crate::hotspots::get(&client.client, address))
+    }
+
+    /// Get the hotspots within `distance` meters of a given location
+    pub fn near(client: &Client, lat: f64, lon: f64, distance: u32) -> Iter<Hotspot> {
+        client.iter(crate::hotspots::near(&client.client, lat, lon, distance))
+    }
+
+    /// Get the hotspots near a given location as pages, starting after the
+    /// page for the given cursor if any
+    pub fn near_pages(
+        client: &Client,
+        lat: f64,
+        lon: f64,
+        distance: u32,
+        cursor: Option<&str>,
+    ) -> Iter<Page<Hotspot>> {
+        client.iter(crate::hotspots::near_pages(
+            &client.client,
+            lat,
+            lon,
+            distance,
+            cursor,
+        ))
+    }
+
+    /// Get the hotspots within a given box
+    pub fn in_box(
+        client: &Client,
+        swlat: f64,
+        swlon: f64,
+        nelat: f64,
+        nelon: f64,
+    ) -> Iter<Hotspot> {
+        client.iter(crate::hotspots::in_box(
+            &client.client,
+            swlat,
+            swlon,
+            nelat,
+            nelon,
+        ))
+    }
+
+    /// Get the hotspots within a given box as pages, starting after the page
+    /// for the given cursor if any
+    pub fn in_box_pages(
+        client: &Client,
+        swlat: f64,
+        swlon: f64,
+        nelat: f64,
+        nelon: f64,
+        cursor: Option<&str>,
+    ) -> Iter<Page<Hotspot>> {
+        client.iter(crate::hotspots::in_box_pages(
+            &client.client,
+            swlat,
+            swlon,
+            nelat,
+            nelon,
+            cursor,
+        ))
+    }
+
+    /// Get the hotspots located in a given h3 hex
+    pub fn in_hex(client: &Client, h3_index: &str) -> Iter<Hotspot> {
+        client.iter(crate::hotspots::in_hex(&client.client, h3_index))
+    }
+
+    /// Get the hotspots located in a given h3 hex as pages, starting after
+    /// the page for the given cursor if any
+    pub fn in_hex_pages(
+        client: &Client,
+        h3_index: &str,
+        cursor: Option<&str>,
+    ) -> Iter<Page<Hotspot>> {
+        client.iter(crate::hotspots::in_hex_pages(
+            &client.client,
+            h3_index,
+            cursor,
+        ))
+    }
+
+    /// Get the hotspots that witness a given hotspot
+    pub fn witnesses(client: &Client, address: &str) -> Iter<Witness> {
+        client.iter(crate::hotspots::witnesses(&client.client, address))
+    }
+
+    /// Get the hotspots a given hotspot witnesses
+    pub fn witnessed(client: &Client, address: &str) -> Iter<Witness> {
+        client.iter(crate::hotspots::witnessed(&client.client, address))
+    }
+
+    /// Fetches the activity of a given hotspot
+    pub fn activity(
+        client: &Client,
+        address: &str,
+        query: &QueryActivity,
+    ) -> Iter<TransactionEnvelope> {
+        client.iter(crate::hotspots::activity(&client.client, address, query))
+    }
+
+    /// Fetches the activity of a given hotspot as pages, starting after the
+    /// page for the given cursor if any
+    pub fn activity_pages(
+        client: &Client,
+        address: &str,
+        query: &QueryActivity,
+        cursor: Option<&str>,
+    ) -> Iter<Page<TransactionEnvelope>> {
+        client.iter(crate::hotspots::activity_pages(
+            &client.client,
+            address,
+            query,
+            cursor,
+        ))
+    }
+
+    /// Get rewards for a hotspot
+    pub fn rewards(client: &Client, address: &str, query: &QueryTimeRange) -> Iter<Reward> {
+        client.iter(crate::hotspots::rewards(&client.client, address, query))
+    }
+
+    /// Get rewards for a hotspot as pages, starting after the page for the
+    /// given cursor if any
+    pub fn rewards_pages(
+        client: &Client,
+        address: &str,
+        query: &QueryTimeRange,
+        cursor: Option<&str>,
+    ) -> Iter<Page<Reward>> {
+        client.iter(crate::hotspots::rewards_pages(
+            &client.client,
+            address,
+            query,
+            cursor,
+        ))
+    }
+
+    /// Get the sum of the rewards for a hotspot in the given timeframe, or
+    /// the sums for each bucket of the given size when a bucket is given
+    pub fn rewards_sum(
+        client: &Client,
+        address: &str,
+        query: &QueryTimeRange,
+        bucket: Option<Bucket>,
+    ) -> Result<RewardSums> {
+        client.block_on(crate::hotspots::rewards_sum(
+            &client.client,
+            address,
+            query,
+            bucket,
+        ))
+    }
+}
+
+pub mod cities {
+    use super::{Client, Iter};
+    use crate::{
+        models::{City, Hotspot},
+        Page, Result,
+    };
+
+    /// Get all cities with hotspots
+    pub fn all(client: &Client) -> Iter<City> {
+        client.iter(crate::cities::all(&client.client))
+    }
+
+    /// Get all cities with hotspots as pages, starting after the page for
+    /// the given cursor if any
+    pub fn all_pages(client: &Client, cursor: Option<&str>) -> Iter<Page<City>> {
+        client.iter(crate::cities::all_pages(&client.client, cursor))
+    }
+
+    /// Get the cities with hotspots whose name matches a given search term
+    pub fn search(client: &Client, term: &str) -> Iter<City> {
+        client.iter(crate::cities::search(&client.client, term))
+    }
+
+    /// Get the cities with hotspots whose name matches a given search term
+    /// as pages, starting after the page for the given cursor if any
+    pub fn search_pages(client: &Client, term: &str, cursor: Option<&str>) -> Iter<Page<City>> {
+        client.iter(crate::cities::search_pages(&client.client, term, cursor))
+    }
+
+    /// Get a specific city by its id
+    pub fn get(client: &Client, city_id: &str) -> Result<City> {
+        client.block_on(crate::cities::get(&client.client, city_id))
+    }
+
+    /// Get all hotspots in a given city
+    pub fn hotspots(client: &Client, city_id: &str) -> Iter<Hotspot> {
+        client.iter(crate::cities::hotspots(&client.client, city_id))
+    }
+
+    /// Get all hotspots in a given city as pages, starting after the page
+    /// for the given cursor if any
+    pub fn hotspots_pages(
+        client: &Client,
+        city_id: &str,
+        cursor: Option<&str>,
+    ) -> Iter<Page<Hotspot>> {
+        client.iter(crate::cities::hotspots_pages(
+            &client.client,
+            city_id,
+            cursor,
+        ))
+    }
+}
+
+pub mod locations {
+    use super::Client;
+    use crate::{models::Geocode, Result};
+
+    /// Get the geocode of a given h3 hex location
+    pub fn get(client: &Client, location: &str) -> Result<Geocode> {
+        client.block_on(crate::locations::get(&client.client, location))
+    }
+}
+
+pub mod blocks {
+    use super::{Client, Iter};
+    use crate::{
+        models::{transactions::TransactionEnvelope, Descriptions},
+        Page, Result,
+    };
+
+    /// Get the current height of the blockchain
+    pub fn height(client: &Client) -> Result<u64> {
+        client.block_on(crate::blocks::height(&client.client))
+    }
+
+    /// Retrieves block descriptions. Blocks descriptions are paged.
+    /// A cursor field will be in the response when more results are available.
+    pub fn descriptions(client: &Client, cursor: Option<&str>) -> Result<Descriptions> {
+        client.block_on(crate::blocks::descriptions(&client.client, cursor))
+    }
+
+    /// Get the transactions in the block at the given height
+    pub fn transactions_at_height(client: &Client, block: u64) -> Iter<TransactionEnvelope> {
+        client.iter(crate::blocks::transactions_at_height(&client.client, block))
+    }
+
+    /// Get the transactions in the block at the given height as pages, starting
+    /// after the page for the given cursor if any
+    pub fn transactions_at_height_pages(
+        client: &Client,
+        block: u64,
+        cursor: Option<&str>,
+    ) -> Iter<Page<TransactionEnvelope>> {
+        client.iter(crate::blocks::transactions_at_height_pages(
+            &client.client,
+            block,
+            cursor,
+        ))
+    }
+
+    /// Get the transactions in the block with the given hash
+    pub fn transactions_at_block_hash(client: &Client, hash: &str) -> Iter<TransactionEnvelope> {
+        client.iter(crate::blocks::transactions_at_block_hash(
+            &client.client,
+            hash,
+        ))
+    }
+
+    /// Get the transactions in the block with the given hash as pages, starting
+    /// after the page for the given cursor if any
+    pub fn transactions_at_block_hash_pages(
+        client: &Client,
+        hash: &str,
+        cursor: Option<&str>,
+    ) -> Iter<Page<TransactionEnvelope>> {
+        client.iter(crate::blocks::transactions_at_block_hash_pages(
+            &client.client,
+            hash,
+            cursor,
+        ))
+    }
+}
+
+pub mod oracle {
+    use super::Client;
+    use crate::{models::OraclePrediction, Result};
+
+    pub mod prices {
+        use super::super::{Client, Iter};
+        use crate::{models::OraclePrice, Page, Result};
+
+        /// Fetch all inferred oracle prices
+        pub fn all(client: &Client) -> Iter<OraclePrice> {
+            client.iter(crate::oracle::prices::all(&client.client))
+        }
+
+        /// Fetch all inferred oracle prices as pages, starting after the page
+        /// for the given cursor if any
+        pub fn all_pages(client: &Client, cursor: Option<&str>) -> Iter<Page<OraclePrice>> {
+            client.iter(crate::oracle::prices::all_pages(&client.client, cursor))
+        }
+
+        /// Get the current valid oracle price
+        pub fn current(client: &Client) -> Result<OraclePrice> {
+            client.block_on(crate::oracle::prices::current(&client.client))
+        }
+
+        /// Get the oracle price that was valid at the given block
+        pub fn at_block(client: &Client, block: u64) -> Result<OraclePrice> {
+            client.block_on(crate::oracle::prices::at_block(&client.client, block))
+        }
+    }
+
+    /// Fetches a list of oracle price predictions based on received oracle
+    /// reports and the current oracle price.
+    pub fn predictions(client: &Client) -> Result<Vec<OraclePrediction>> {
+        client.block_on(crate::oracle::predictions(&client.client))
+    }
+}
+
+pub mod ouis {
+    use super::{Client, Iter};
+    use crate::{
+        models::{Oui, OuiStats},
+        Page, Result,
+    };
+
+    /// Get all ouis
+    pub fn all(client: &Client) -> Iter<Oui> {
+        client.iter(crate::ouis::all(&client.client))
+    }
+
+    /// Get all ouis as pages, starting after the page for the given cursor if
+    /// any
+    pub fn all_pages(client: &Client, cursor: Option<&str>) -> Iter<Page<Oui>> {
+        client.iter(crate::ouis::all_pages(&client.client, cursor))
+    }
+
+    /// Get a specific oui
+    pub fn get(client: &Client, oui: u64) -> Result<Oui> {
+        client.block_on(crate::ouis::get(&client.client, oui))
+    }
+
+    /// Get the last assigned oui
+    pub fn last(client: &Client) -> Result<Oui> {
+        client.block_on(crate::ouis::last(&client.client))
+    }
+
+    /// Get statistics for ouis
+    pub fn stats(client: &Client) -> Result<OuiStats> {
+        client.block_on(crate::ouis::stats(&client.client))
+    }
+}
+
+pub mod validators {
+    use super::{Client, Iter};
+    use crate::{
+        models::{Bucket, QueryTimeRange, Reward, RewardSums, Validator, ValidatorStats},
+        Page, Result,
+    };
+
+    /// Get all known validators
+    pub fn all(client: &Client) -> Iter<Validator> {
+        client.iter(crate::validators::all(&client.client))
+    }
+
+    /// Get all known validators as pages, starting after the page for the
+    /// given cursor if any
+    pub fn all_pages(client: &Client, cursor: Option<&str>) -> Iter<Page<Validator>> {
+        client.iter(crate::validators::all_pages(&client.client, cursor))
+    }
+
+    /// Get a specific validator
+    pub fn get(client: &Client, address: &str) -> Result<Validator> {
+        client.block_on(crate::validators::get(&client.client, address))
+    }
+
+    /// Get stats for validators
+    pub fn stats(client: &Client) -> Result<ValidatorStats> {
+        client.block_on(crate::validators::stats(&client.client))
+    }
+
+    /// Get rewards for a validator
+    pub fn rewards(client: &Client, address: &str, query: &QueryTimeRange) -> Iter<Reward> {
+        client.iter(crate::validators::rewards(&client.client, address, query))
+    }
+
+    /// Get rewards for a validator as pages, starting after the page for the
+    /// given cursor if any
+    pub fn rewards_pages(
+        client: &Client,
+        address: &str,
+        query: &QueryTimeRange,
+        cursor: Option<&str>,
+    ) -> Iter<Page<Reward>> {
+        client.iter(crate::validators::rewards_pages(
+            &client.client,
+            address,
+            query,
+            cursor,
+        ))
+    }
+
+    /// Get the sum of the rewards for a validator in the given timeframe, or
+    /// the sums for each bucket of the given size when a bucket is given
+    pub fn rewards_sum(
+        client: &Client,
+        address: &str,
+        query: &QueryTimeRange,
+        bucket: Option<Bucket>,
+    ) -> Result<RewardSums> {
+        client.block_on(crate::validators::rewards_sum(
+            &client.client,
+            address,
+            query,
+            bucket,
+        ))
+    }
+}
+
+pub mod vars {
+    use super::Client;
+    use crate::Result;
+
+    /// Get all chain variables
+    pub fn get(client: &Client) -> Result<serde_json::Map<String, serde_json::Value>> {
+        client.block_on(crate::vars::get(&client.client))
+    }
+
+    /// Get the chain variables with the given names
+    pub fn get_named(
+        client: &Client,
+        keys: &[&str],
+    ) -> Result<serde_json::Map<String, serde_json::Value>> {
+        client.block_on(crate::vars::get_named(&client.client, keys))
+    }
+}
+
+pub mod transactions {
+    use super::Client;
+    use crate::{models::transactions::TransactionEnvelope, Result};
+
+    /// Get a specific transaction by its hash
+    pub fn get(client: &Client, hash: &str) -> Result<TransactionEnvelope> {
+        client.block_on(crate::transactions::get(&client.client, hash))
+    }
+}
+
+pub mod pending_transactions {
+    use super::Client;
+    use crate::{models::transactions::PendingTxnStatus, Result};
+
+    /// Submit a transaction to the blockchain
+    pub fn submit<T>(client: &Client, txn: T) -> Result<PendingTxnStatus>
+    where
+        T: AsRef<[u8]>,
+    {
+        client.block_on(crate::pending_transactions::submit(&client.client, txn))
+    }
+
+    /// Get the status for a specific pending transaction hash
+    pub fn get(client: &Client, hash: &str) -> Result<PendingTxnStatus> {
+        client.block_on(crate::pending_transactions::get(&client.client, hash))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transport::MemoryTransport;
+    use serde_json::json;
+
+    #[test]
+    fn blocking() {
+        let transport = MemoryTransport::new()
+            .with_json("/blocks/height", json!({"data": {"height": 10}}))
+            .with_json("/ouis", json!({"data": [], "cursor": "a"}))
+            .with_json_query(
+                "/ouis",
+                &[("cursor", "a")],
+                json!({"data": [{"oui": 1, "owner": "a", "nonce": 1, "addresses": [], "subnets": []}]}),
+            );
+        let client = crate::Client::builder()
+            .transport(transport)
+            .build_blocking()
+            .expect("client");
+        assert_eq!(blocks::height(&client).expect("height"), 10);
+        let ouis: Vec<_> = ouis::all(&client).collect::<Result<_>>().expect("ouis");
+        assert_eq!(ouis.len(), 1);
+        assert_eq!(ouis[0].oui, 1);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    models::{transactions::Transaction, Descriptions, Height},
+    models::{transactions::TransactionEnvelope, Descriptions, Height},
     *,
 };
 
@@ -12,24 +12,74 @@ pub async fn height(client: &Client) -> Result<u64> {
 /// Retrieves block descriptions. Blocks descriptions are paged.
 /// A cursor field will be in the response when more results are available.
 pub async fn descriptions(client: &Client, cursor: Option<&str>) -> Result<Descriptions> {
-    let query = cursor.map_or(NO_QUERY.to_vec(), |c| vec![c]);
+    let query = cursor.map_or(vec![], |c| vec![("cursor", c)]);
     client
         .fetch_data("/blocks", &query)
         .await
         .map(|Data { data, cursor }| Descriptions { data, cursor })
 }
 
-pub fn transactions_at_height(client: &Client, block: u64) -> Stream<Transaction> {
+pub fn transactions_at_height(client: &Client, block: u64) -> Stream<TransactionEnvelope> {
     client.fetch_stream(format!("/blocks/{}/transactions", block).as_str(), NO_QUERY)
 }
 
-pub fn transactions_at_block_hash(client: &Client, hash: &str) -> Stream<Transaction> {
-    client.fetch_stream(
-        format!("/blocks/hash/{}/transactions", hash).as_str(),
+/// Get the transactions in the block at the given height as pages, starting
+/// after the page for the given cursor if any
+pub fn transactions_at_height_pages(
+    client: &Client,
+    block: u64,
+    cursor: Option<&str>,
+) -> Stream<Page<TransactionEnvelope>> {
+    client.fetch_pages(
+        format!("/blocks/{}/transactions", block).as_str(),
         NO_QUERY,
+        cursor,
     )
 }
 
+pub fn transactions_at_block_hash(client: &Client, hash: &str) -> Stream<TransactionEnvelope> {
+    let block_hash = hash.to_string();
+    client
+        .fetch_stream(
+            format!("/blocks/hash/{}/transactions", hash).as_str(),
+            NO_QUERY,
+        )
+        .map_ok(move |txn| with_block_hash(txn, &block_hash))
+        .boxed()
+}
+
+/// Get the transactions in the block with the given hash as pages, starting
+/// after the page for the given cursor if any
+pub fn transactions_at_block_hash_pages(
+    client: &Client,
+    hash: &str,
+    cursor: Option<&str>,
+) -> Stream<Page<TransactionEnvelope>> {
+    let block_hash = hash.to_string();
+    client
+        .fetch_pages(
+            format!("/blocks/hash/{}/transactions", hash).as_str(),
+            NO_QUERY,
+            cursor,
+        )
+        .map_ok(move |page| Page {
+            items: page
+                .items
+                .into_iter()
+                .map(|txn| with_block_hash(txn, &block_hash))
+                .collect(),
+            cursor: page.cursor,
+        })
+        .boxed()
+}
+
+fn with_block_hash(txn: TransactionEnvelope, block_hash: &str) -> TransactionEnvelope {
+    TransactionEnvelope {
+        block_hash: Some(block_hash.to_string()),
+        ..txn
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -48,7 +98,7 @@ mod test {
         let descriptions = blocks::descriptions(&client, None)
             .await
             .expect("descriptions");
-        assert!(descriptions.data.len() > 0);
+        assert!(!descriptions.data.is_empty());
     }
 
     #[test]
@@ -60,6 +110,7 @@ mod test {
             .await
             .expect("transactions");
         assert_eq!(transactions.len(), 10);
+        assert!(transactions.iter().all(|txn| txn.height == 1378232));
     }
 
     #[test]
@@ -74,5 +125,9 @@ mod test {
         .await
         .expect("transactions");
         assert_eq!(transactions.len(), 10);
+        assert!(transactions
+            .iter()
+            .all(|txn| txn.block_hash.as_deref()
+                == Some("BogDArZ5QxbgSd4wLmCS8NRtRzwvCA5fGn1V2TtsYoU")));
     }
 }
@@ -0,0 +1,334 @@
+use crate::observer::RequestObserver;
+use crate::transport::{ReqwestTransport, Transport};
+use crate::{
+    Cache, Client, Endpoints, Error, RateLimiter, Result, RetryPolicy, DEFAULT_API_KEY_HEADER,
+    DEFAULT_BASE_URL, DEFAULT_TIMEOUT, DEFAULT_USER_AGENT,
+};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use std::{sync::Arc, time::Duration};
+
+/// A builder to configure and construct a `Client`.
+///
+/// The builder exposes the transport settings of the underlying HTTP client.
+/// All settings are optional and default to values suitable for the public
+/// Helium API.
+///
+/// ```no_run
+/// use helium_api::Client;
+/// use std::time::Duration;
+///
+/// # fn main() -> helium_api::Result {
+/// let client = Client::builder()
+///     .base_url("https://my-node.example.com/v1")
+///     .connect_timeout(Duration::from_secs(5))
+///     .bearer_auth("my-secret-token")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ClientBuilder {
+    base_url: String,
+    user_agent: String,
+    timeout: Duration,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    headers: Vec<(String, String)>,
+    proxy: Option<reqwest::Proxy>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    transport: Option<Arc<dyn Transport>>,
+    retry: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<Cache>,
+    endpoints: Option<Endpoints>,
+    observer: Option<Arc<dyn RequestObserver>>,
+    prefetch: usize,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self::new(DEFAULT_BASE_URL)
+    }
+}
+
+impl ClientBuilder {
+    /// Create a new builder for the given base URL. The library will use
+    /// absolute paths based on this base URL.
+    pub fn new<S: Into<String>>(base_url: S) -> Self {
+        Self {
+            base_url: base_url.into(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+            connect_timeout: None,
+            read_timeout: None,
+            headers: vec![],
+            proxy: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            transport: None,
+            retry: RetryPolicy::default(),
+            rate_limiter: None,
+            cache: None,
+            endpoints: None,
+            observer: None,
+            prefetch: 0,
+        }
+    }
+
+    /// Set the base URL for the client.
+    pub fn base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Fail over between the given base URLs, in order of preference. See
+    /// `Endpoints` for how endpoints are selected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no base URL is given.
+    pub fn base_urls<I, S>(self, base_urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.endpoints(Endpoints::new(base_urls))
+    }
+
+    /// Fail over between the given endpoints. This allows clients that were
+    /// built separately to share the health state of the endpoints.
+    pub fn endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.base_url = endpoints.urls()[0].clone();
+        self.endpoints = Some(endpoints);
+        self
+    }
+
+    /// Set the user agent sent with every request.
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Set the total timeout for a request, from connecting until the
+    /// response body has been read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the timeout for only the connect phase of a request.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for each read operation while receiving a response.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Add a header that is sent with every request. Invalid header names or
+    /// values are reported when the client is built.
+    pub fn header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Authenticate every request with the given bearer token.
+    pub fn bearer_auth<T: AsRef<str>>(self, token: T) -> Self {
+        self.header(AUTHORIZATION.as_str(), format!("Bearer {}", token.as_ref()))
+    }
+
+    /// Authenticate every request with the given API key in the
+    /// `DEFAULT_API_KEY_HEADER` header.
+    pub fn api_key<T: Into<String>>(self, key: T) -> Self {
+        self.header(DEFAULT_API_KEY_HEADER, key)
+    }
+
+    /// Route all requests through the given proxy.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Set the maximum number of idle connections kept per host.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set how long idle connections are kept in the pool.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Use a pre-built `reqwest::Client` for all requests. When set, the
+    /// transport settings of this builder (user agent, timeouts, headers,
+    /// proxy and pool limits) are ignored in favor of the given client.
+    pub fn reqwest_client(self, client: reqwest::Client) -> Self {
+        self.transport(ReqwestTransport::from(client))
+    }
+
+    /// Use the given transport for all requests. When set, the transport
+    /// settings of this builder (user agent, timeouts, headers, proxy and
+    /// pool limits) are ignored.
+    pub fn transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Set the policy used to retry requests that fail with a transient
+    /// error. Use `RetryPolicy::none()` to disable retries.
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Limit requests to `requests_per_second` on average with bursts of up
+    /// to `burst` requests. The limit is shared by the built client and all
+    /// its clones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `requests_per_second` is not a positive number.
+    pub fn rate_limit(self, requests_per_second: f64, burst: u32) -> Self {
+        self.rate_limiter(RateLimiter::new(requests_per_second, burst))
+    }
+
+    /// Use the given rate limiter for all requests. This allows clients that
+    /// were built separately to share a single limit.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Cache responses in the given cache. The cache is shared by the built
+    /// client and all its clones, and can be shared with other clients.
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Fetch up to `depth` pages of a paged request in the background, ahead
+    /// of the consumer of the stream. This speeds up crawls of large paged
+    /// endpoints at the cost of holding up to `depth` extra pages in memory.
+    /// Prefetching is disabled by default.
+    ///
+    /// The background task is spawned on the tokio runtime the stream is
+    /// first polled on, so paged requests of a client with a prefetch depth
+    /// must be consumed from within a running tokio runtime.
+    pub fn prefetch(mut self, depth: usize) -> Self {
+        self.prefetch = depth;
+        self
+    }
+
+    /// Report all requests to the given observer.
+    pub fn observer<O: RequestObserver + 'static>(mut self, observer: O) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Build the `Client`. This fails if a configured header is invalid or
+    /// the underlying HTTP client can not be initialized.
+    pub fn build(self) -> Result<Client> {
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => {
+                let headers = self.default_headers()?;
+                let mut builder = reqwest::Client::builder()
+                    .gzip(true)
+                    .user_agent(self.user_agent)
+                    .timeout(self.timeout)
+                    .default_headers(headers);
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = self.read_timeout {
+                    builder = builder.read_timeout(timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(max) = self.pool_max_idle_per_host {
+                    builder = builder.pool_max_idle_per_host(max);
+                }
+                if let Some(timeout) = self.pool_idle_timeout {
+                    builder = builder.pool_idle_timeout(timeout);
+                }
+                Arc::new(ReqwestTransport::from(builder.build()?))
+            }
+        };
+        Ok(Client {
+            base_url: self.base_url,
+            transport,
+            retry: self.retry,
+            rate_limiter: self.rate_limiter,
+            cache: self.cache,
+            endpoints: self.endpoints,
+            observer: self.observer,
+            prefetch: self.prefetch,
+        })
+    }
+
+    /// The headers sent with every request. Credentials are marked sensitive,
+    /// so they are redacted from debug output and traces.
+    fn default_headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let header_name =
+                HeaderName::from_bytes(name.as_bytes()).map_err(|_| Error::header(name))?;
+            let mut header_value = HeaderValue::from_str(value).map_err(|_| Error::header(name))?;
+            header_value.set_sensitive(
+                header_name == AUTHORIZATION || header_name == DEFAULT_API_KEY_HEADER,
+            );
+            headers.insert(header_name, header_value);
+        }
+        Ok(headers)
+    }
+
+    /// Build a `blocking::Client`. This fails like `build`, or if the runtime
+    /// for the blocking client can not be created.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> Result<crate::blocking::Client> {
+        crate::blocking::Client::new(self.build()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default() {
+        let client = ClientBuilder::default().build().expect("client");
+        assert_eq!(client.base_url(), DEFAULT_BASE_URL);
+    }
+
+    #[test]
+    fn invalid_header() {
+        let result = ClientBuilder::default()
+            .header("x-api-key", "bad\nvalue")
+            .build();
+        assert!(matches!(result, Err(Error::Header(name)) if name == "x-api-key"));
+    }
+
+    #[test]
+    fn sensitive_headers() {
+        let headers = ClientBuilder::default()
+            .bearer_auth("token")
+            .api_key("key")
+            .header("x-request-source", "tests")
+            .default_headers()
+            .expect("headers");
+        assert!(headers[AUTHORIZATION].is_sensitive());
+        assert!(headers[DEFAULT_API_KEY_HEADER].is_sensitive());
+        assert!(!headers["x-request-source"].is_sensitive());
+    }
+}
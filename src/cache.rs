@@ -0,0 +1,365 @@
+use crate::{transport::Request, Result};
+use bytes::Bytes;
+use reqwest::Method;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A cache for API responses.
+///
+/// Only successful GET requests are cached. Resources that never change once
+/// they exist are cached by default:
+///
+/// * blocks and their transactions by height or hash
+/// * transactions by hash, as returned by `transactions::get`
+/// * oracle prices at a given block, as returned by `oracle::prices::at_block`
+///
+/// Resources that change over time, like `accounts::get` and `hotspots::get`,
+/// are only cached when a time to live is configured with `mutable_ttl`.
+///
+/// Responses are kept in memory up to the given capacity, evicting the least
+/// recently used entry first. With `with_dir` responses for immutable
+/// resources are also stored on disk, so they survive the process.
+///
+/// A cache is cheap to clone and all clones share the same entries. This
+/// includes the cache of a `Client` and all clones of that client.
+#[derive(Clone, Debug)]
+pub struct Cache {
+    capacity: usize,
+    mutable_ttl: Option<Duration>,
+    dir: Option<PathBuf>,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    entries: HashMap<String, Entry>,
+    /// The keys of all entries by their last use, least recent first
+    lru: BTreeMap<u64, String>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl State {
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.lru.remove(&entry.used);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    body: Bytes,
+    stored_at: Instant,
+    used: u64,
+    mutable: bool,
+}
+
+/// Statistics on the lookups in a `Cache`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The number of requests served from the cache.
+    pub hits: u64,
+    /// The number of cacheable requests that were not in the cache.
+    pub misses: u64,
+    /// The number of entries held in memory.
+    pub entries: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Immutable,
+    Mutable,
+}
+
+impl Cache {
+    /// Create an in-memory cache holding up to `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            mutable_ttl: None,
+            dir: None,
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// Also cache resources that change over time, like accounts and
+    /// hotspots, for up to the given duration.
+    pub fn mutable_ttl(mut self, ttl: Duration) -> Self {
+        self.mutable_ttl = Some(ttl);
+        self
+    }
+
+    /// Store responses for immutable resources in the given directory as
+    /// well. The directory is created if it does not exist.
+    pub fn with_dir<P: Into<PathBuf>>(mut self, dir: P) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        self.dir = Some(dir);
+        Ok(self)
+    }
+
+    /// The statistics of this cache and all its clones.
+    pub fn stats(&self) -> CacheStats {
+        let state = self.state.lock().expect("cache state");
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            entries: state.entries.len(),
+        }
+    }
+
+    /// Remove all entries held in memory. Entries stored on disk are kept.
+    pub fn clear(&self) {
+        let mut state = self.state.lock().expect("cache state");
+        state.entries.clear();
+        state.lru.clear();
+    }
+
+    /// Whether responses to the given request are cached.
+    pub(crate) fn accepts(&self, request: &Request) -> bool {
+        self.kind(request).is_some()
+    }
+
+    /// Look up the response body for the given request. Entries stored on
+    /// disk are read on the blocking thread pool of the runtime.
+    pub(crate) async fn get(&self, request: &Request) -> Option<Bytes> {
+        let kind = self.kind(request)?;
+        let key = request.url();
+        if let Some(body) = self.get_entry(&key) {
+            return Some(body);
+        }
+        let stored = match (&self.dir, kind) {
+            (Some(dir), Kind::Immutable) => {
+                let (dir, file_key) = (dir.clone(), key.clone());
+                tokio::task::spawn_blocking(move || read_file(&dir, &file_key))
+                    .await
+                    .ok()
+                    .flatten()
+            }
+            _ => None,
+        };
+        let mut state = self.state.lock().expect("cache state");
+        match stored {
+            Some(body) => {
+                state.hits += 1;
+                self.insert_entry(&mut state, key, body.clone(), false);
+                Some(body)
+            }
+            None => {
+                state.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Look up the entry held in memory for the given key, removing it if it
+    /// expired.
+    fn get_entry(&self, key: &str) -> Option<Bytes> {
+        let mut state = self.state.lock().expect("cache state");
+        state.clock += 1;
+        let clock = state.clock;
+        let entry = state.entries.get_mut(key)?;
+        let expired = entry.mutable
+            && !matches!(self.mutable_ttl, Some(ttl) if entry.stored_at.elapsed() <= ttl);
+        if expired {
+            state.remove(key);
+            return None;
+        }
+        let used = std::mem::replace(&mut entry.used, clock);
+        let body = entry.body.clone();
+        state.lru.remove(&used);
+        state.lru.insert(clock, key.to_string());
+        state.hits += 1;
+        Some(body)
+    }
+
+    /// Store the response body for the given request. Immutable resources
+    /// are written to disk on the blocking thread pool of the runtime.
+    pub(crate) async fn insert(&self, request: &Request, body: Bytes) {
+        let kind = match self.kind(request) {
+            Some(kind) => kind,
+            None => return,
+        };
+        let key = request.url();
+        if let (Some(dir), Kind::Immutable) = (&self.dir, kind) {
+            let (dir, file_key, file_body) = (dir.clone(), key.clone(), body.clone());
+            let write = move || write_file(&dir, &file_key, &file_body);
+            // The disk store is best effort, a failure to write only costs a
+            // future request.
+            let _ = tokio::task::spawn_blocking(write).await;
+        }
+        let mut state = self.state.lock().expect("cache state");
+        self.insert_entry(&mut state, key, body, kind == Kind::Mutable);
+    }
+
+    fn insert_entry(&self, state: &mut State, key: String, body: Bytes, mutable: bool) {
+        if self.capacity == 0 {
+            return;
+        }
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = state.lru.pop_first() {
+                state.entries.remove(&oldest);
+            }
+        }
+        state.clock += 1;
+        let entry = Entry {
+            body,
+            stored_at: Instant::now(),
+            used: state.clock,
+            mutable,
+        };
+        state.lru.insert(entry.used, key.clone());
+        if let Some(replaced) = state.entries.insert(key, entry) {
+            state.lru.remove(&replaced.used);
+        }
+    }
+
+    fn kind(&self, request: &Request) -> Option<Kind> {
+        if request.method != Method::GET {
+            return None;
+        }
+        match classify(&request.path) {
+            Some(Kind::Mutable) if self.mutable_ttl.is_none() => None,
+            kind => kind,
+        }
+    }
+}
+
+/// Classify the given API path by whether the resource it refers to can
+/// change once it exists.
+fn classify(path: &str) -> Option<Kind> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    match segments.as_slice() {
+        ["blocks", height] if is_number(height) => Some(Kind::Immutable),
+        ["blocks", height, "transactions"] if is_number(height) => Some(Kind::Immutable),
+        ["blocks", "hash", _, "transactions"] => Some(Kind::Immutable),
+        ["transactions", _] => Some(Kind::Immutable),
+        ["oracle", "prices", block] if is_number(block) => Some(Kind::Immutable),
+        ["accounts", address] if *address != "rich" => Some(Kind::Mutable),
+        ["hotspots", _] => Some(Kind::Mutable),
+        _ => None,
+    }
+}
+
+/// The file an entry is stored in. Files are named by a hash of the key and
+/// start with the key itself on the first line to detect collisions.
+fn file_path(dir: &Path, key: &str) -> PathBuf {
+    // 64 bit FNV-1a, which unlike the std hasher is stable across releases
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    dir.join(format!("{:016x}.json", hash))
+}
+
+fn read_file(dir: &Path, key: &str) -> Option<Bytes> {
+    let data = fs::read(file_path(dir, key)).ok()?;
+    let split = data.iter().position(|b| *b == b'\n')?;
+    if &data[..split] != key.as_bytes() {
+        return None;
+    }
+    Some(Bytes::from(data).slice(split + 1..))
+}
+
+fn write_file(dir: &Path, key: &str, body: &[u8]) -> io::Result<()> {
+    let mut data = Vec::with_capacity(key.len() + 1 + body.len());
+    data.extend_from_slice(key.as_bytes());
+    data.push(b'\n');
+    data.extend_from_slice(body);
+    fs::write(file_path(dir, key), data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio::test;
+
+    fn get(path: &str) -> Request {
+        Request::get("http://localhost/v1", path, crate::NO_QUERY).expect("request")
+    }
+
+    #[test]
+    async fn classify_paths() {
+        assert_eq!(classify("/blocks/100"), Some(Kind::Immutable));
+        assert_eq!(classify("/blocks/100/transactions"), Some(Kind::Immutable));
+        assert_eq!(classify("/blocks/height"), None);
+        assert_eq!(classify("/transactions/abc"), Some(Kind::Immutable));
+        assert_eq!(classify("/oracle/prices/100"), Some(Kind::Immutable));
+        assert_eq!(classify("/oracle/prices/current"), None);
+        assert_eq!(classify("/accounts/abc"), Some(Kind::Mutable));
+        assert_eq!(classify("/accounts/rich"), None);
+        assert_eq!(classify("/accounts/abc/hotspots"), None);
+    }
+
+    #[test]
+    async fn lru() {
+        let cache = Cache::new(2);
+        for path in ["/transactions/a", "/transactions/b"] {
+            cache.insert(&get(path), Bytes::from(path)).await;
+        }
+        assert!(cache.get(&get("/transactions/a")).await.is_some());
+        cache
+            .insert(&get("/transactions/c"), Bytes::from("c"))
+            .await;
+        assert!(cache.get(&get("/transactions/b")).await.is_none());
+        assert!(cache.get(&get("/transactions/a")).await.is_some());
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 2,
+                misses: 1,
+                entries: 2
+            }
+        );
+    }
+
+    #[test]
+    async fn lru_reinsert() {
+        let cache = Cache::new(2);
+        for path in ["/transactions/a", "/transactions/b", "/transactions/a"] {
+            cache.insert(&get(path), Bytes::from(path)).await;
+        }
+        cache
+            .insert(&get("/transactions/c"), Bytes::from("c"))
+            .await;
+        assert!(cache.get(&get("/transactions/b")).await.is_none());
+        assert!(cache.get(&get("/transactions/a")).await.is_some());
+        let state = cache.state.lock().expect("cache state");
+        assert_eq!(state.entries.len(), state.lru.len());
+    }
+
+    #[test]
+    async fn mutable() {
+        let request = get("/accounts/abc");
+        let cache = Cache::new(10);
+        assert!(!cache.accepts(&request));
+        let cache = Cache::new(10).mutable_ttl(Duration::ZERO);
+        cache.insert(&request, Bytes::from("account")).await;
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(cache.get(&request).await.is_none());
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    async fn disk() {
+        let dir = std::env::temp_dir().join(format!("helium-api-cache-{}", std::process::id()));
+        let request = get("/transactions/abc");
+        Cache::new(10)
+            .with_dir(&dir)
+            .expect("cache")
+            .insert(&request, Bytes::from("txn"))
+            .await;
+        let cache = Cache::new(10).with_dir(&dir).expect("cache");
+        let body = cache.get(&request).await;
+        fs::remove_dir_all(&dir).expect("remove cache");
+        assert_eq!(body.as_deref(), Some(b"txn".as_ref()));
+        assert_eq!(cache.stats().hits, 1);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    models::{City, Hotspot},
+    *,
+};
+
+/// Get all cities with hotspots
+pub fn all(client: &Client) -> Stream<City> {
+    client.fetch_stream("/cities", NO_QUERY)
+}
+
+/// Get all cities with hotspots as pages, starting after the page for the
+/// given cursor if any
+pub fn all_pages(client: &Client, cursor: Option<&str>) -> Stream<Page<City>> {
+    client.fetch_pages("/cities", NO_QUERY, cursor)
+}
+
+/// Get the cities with hotspots whose name matches the given search term
+pub fn search(client: &Client, term: &str) -> Stream<City> {
+    client.fetch_stream("/cities", &[("search", term)])
+}
+
+/// Get the cities with hotspots whose name matches the given search term as
+/// pages, starting after the page for the given cursor if any
+pub fn search_pages(client: &Client, term: &str, cursor: Option<&str>) -> Stream<Page<City>> {
+    client.fetch_pages("/cities", &[("search", term)], cursor)
+}
+
+/// Get a specific city by its id
+pub async fn get(client: &Client, city_id: &str) -> Result<City> {
+    client
+        .fetch(&format!("/cities/{}", city_id), NO_QUERY)
+        .await
+}
+
+/// Get all hotspots in a given city
+pub fn hotspots(client: &Client, city_id: &str) -> Stream<Hotspot> {
+    client.fetch_stream(&format!("/cities/{}/hotspots", city_id), NO_QUERY)
+}
+
+/// Get all hotspots in a given city as pages, starting after the page for
+/// the given cursor if any
+pub fn hotspots_pages(
+    client: &Client,
+    city_id: &str,
+    cursor: Option<&str>,
+) -> Stream<Page<Hotspot>> {
+    client.fetch_pages(&format!("/cities/{}/hotspots", city_id), NO_QUERY, cursor)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio::test;
+
+    #[test]
+    async fn all() {
+        let client = get_test_client();
+        let cities = cities::all(&client).into_vec().await.expect("cities");
+        assert_eq!(cities.len(), 3);
+    }
+
+    #[test]
+    async fn search() {
+        let client = get_test_client();
+        let cities = cities::search(&client, "lisbon")
+            .into_vec()
+            .await
+            .expect("cities");
+        assert_eq!(cities[0].long_city.as_deref(), Some("Lisbon"));
+    }
+
+    #[test]
+    async fn search_pages() {
+        let client = get_test_client();
+        let pages = cities::search_pages(&client, "san", None)
+            .into_vec()
+            .await
+            .expect("pages");
+        assert_eq!(pages.len(), 2);
+        let cursor = pages[0].cursor.as_deref().expect("cursor");
+        let resumed = cities::search_pages(&client, "san", Some(cursor))
+            .into_vec()
+            .await
+            .expect("resumed pages");
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].items[0].city_id, pages[1].items[0].city_id);
+    }
+
+    #[test]
+    async fn get() {
+        let client = get_test_client();
+        let city = cities::get(&client, "bGlzYm9ubGlzYm9ucG9ydHVnYWw")
+            .await
+            .expect("city");
+        assert_eq!(city.hotspot_count, city.online_count + city.offline_count);
+    }
+
+    #[test]
+    async fn hotspots() {
+        let client = get_test_client();
+        let hotspots = cities::hotspots(&client, "bGlzYm9ubGlzYm9ucG9ydHVnYWw")
+            .into_vec()
+            .await
+            .expect("hotspots");
+        assert_eq!(
+            hotspots[0].geocode.city_id.as_deref(),
+            Some("bGlzYm9ubGlzYm9ucG9ydHVnYWw")
+        );
+    }
+}
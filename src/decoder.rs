@@ -0,0 +1,339 @@
+use crate::{Error, Result};
+use serde::de::{DeserializeOwned, Error as _};
+use std::marker::PhantomData;
+
+/// An entry of a page, or the end of the page with the cursor for the next
+/// page if any.
+pub(crate) enum PageChunk<E> {
+    Entry(E),
+    End(Option<String>),
+}
+
+/// The outcome of decoding the part of a page received so far.
+pub(crate) enum Step<E> {
+    /// The next entry of the `data` array
+    Entry(E),
+    /// More of the body is needed to decode the next entry
+    NeedMore,
+    /// The page is complete, with the cursor for the next page if any
+    Done(Option<String>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Data,
+    Cursor,
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    /// Before the opening brace of the envelope
+    Start,
+    /// Before a key of the envelope, or the closing brace after the first
+    Key {
+        first: bool,
+    },
+    /// Between a key and its value
+    Colon(Field),
+    /// Before the value of a key
+    Value(Field),
+    /// After a value of the envelope
+    AfterValue,
+    /// Inside the `data` array, before an entry, or the closing bracket of an
+    /// empty array
+    Entries {
+        first: bool,
+    },
+    /// Inside the `data` array, after an entry
+    AfterEntry,
+    Done,
+}
+
+/// Progress scanning for the end of a JSON value, kept across chunks so a
+/// large value is only scanned once.
+#[derive(Default)]
+struct Scan {
+    len: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+/// Decodes the `Data` envelope of a page incrementally as its body arrives.
+///
+/// Entries of the `data` array are deserialized one at a time, and the body
+/// is only buffered up to the end of the entry being decoded. The `cursor`
+/// is picked up wherever it appears in the envelope, and any other keys are
+/// skipped.
+pub(crate) struct PageDecoder<E> {
+    url: String,
+    buf: Vec<u8>,
+    pos: usize,
+    eof: bool,
+    state: State,
+    scan: Option<Scan>,
+    has_data: bool,
+    cursor: Option<String>,
+    entry: PhantomData<fn() -> E>,
+}
+
+impl<E: DeserializeOwned> PageDecoder<E> {
+    pub(crate) fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            buf: Vec::new(),
+            pos: 0,
+            eof: false,
+            state: State::Start,
+            scan: None,
+            has_data: false,
+            cursor: None,
+            entry: PhantomData,
+        }
+    }
+
+    /// Add the next chunk of the body, dropping what was already decoded.
+    pub(crate) fn push(&mut self, chunk: &[u8]) {
+        self.buf.drain(..self.pos);
+        self.pos = 0;
+        self.buf.extend_from_slice(chunk);
+    }
+
+    /// Mark the end of the body.
+    pub(crate) fn finish(&mut self) {
+        self.eof = true;
+    }
+
+    /// Decode the next entry from the buffered body. `NeedMore` is only
+    /// returned before `finish` was called.
+    pub(crate) fn next(&mut self) -> Result<Step<E>> {
+        loop {
+            if self.state == State::Done {
+                return Ok(Step::Done(self.cursor.take()));
+            }
+            let byte = match self.token()? {
+                Some(byte) => byte,
+                None => return Ok(Step::NeedMore),
+            };
+            match (self.state, byte) {
+                (State::Start, b'{') => {
+                    self.pos += 1;
+                    self.state = State::Key { first: true };
+                }
+                (State::Key { first: true }, b'}') | (State::AfterValue, b'}') => {
+                    self.pos += 1;
+                    if !self.has_data {
+                        let err = serde_json::Error::missing_field("data");
+                        return Err(Error::decode(&self.url, &self.buf, err));
+                    }
+                    self.state = State::Done;
+                }
+                (State::Key { .. }, b'"') => {
+                    let key: String = match self.value()? {
+                        Some(key) => key,
+                        None => return Ok(Step::NeedMore),
+                    };
+                    self.state = State::Colon(match key.as_str() {
+                        "data" => Field::Data,
+                        "cursor" => Field::Cursor,
+                        _ => Field::Other,
+                    });
+                }
+                (State::Colon(field), b':') => {
+                    self.pos += 1;
+                    self.state = State::Value(field);
+                }
+                (State::Value(Field::Data), b'[') => {
+                    self.pos += 1;
+                    self.has_data = true;
+                    self.state = State::Entries { first: true };
+                }
+                (State::Value(Field::Data), _) => return Err(self.syntax("expected an array")),
+                (State::Value(Field::Cursor), _) => match self.value()? {
+                    Some(cursor) => {
+                        self.cursor = cursor;
+                        self.state = State::AfterValue;
+                    }
+                    None => return Ok(Step::NeedMore),
+                },
+                (State::Value(Field::Other), _) => match self.value::<serde::de::IgnoredAny>()? {
+                    Some(_) => self.state = State::AfterValue,
+                    None => return Ok(Step::NeedMore),
+                },
+                (State::AfterValue, b',') => {
+                    self.pos += 1;
+                    self.state = State::Key { first: false };
+                }
+                (State::Entries { first: true }, b']') | (State::AfterEntry, b']') => {
+                    self.pos += 1;
+                    self.state = State::AfterValue;
+                }
+                (State::Entries { .. }, _) => {
+                    return match self.value()? {
+                        Some(entry) => {
+                            self.state = State::AfterEntry;
+                            Ok(Step::Entry(entry))
+                        }
+                        None => Ok(Step::NeedMore),
+                    }
+                }
+                (State::AfterEntry, b',') => {
+                    self.pos += 1;
+                    self.state = State::Entries { first: false };
+                }
+                _ => return Err(self.syntax("unexpected character")),
+            }
+        }
+    }
+
+    /// Skip whitespace and return the next byte, if it was received yet.
+    fn token(&mut self) -> Result<Option<u8>> {
+        while let Some(byte) = self.buf.get(self.pos) {
+            if !byte.is_ascii_whitespace() {
+                return Ok(Some(*byte));
+            }
+            self.pos += 1;
+        }
+        if self.eof {
+            return Err(self.syntax("unexpected end of body"));
+        }
+        Ok(None)
+    }
+
+    /// Deserialize the JSON value starting at the current position once it
+    /// was received completely.
+    fn value<T: DeserializeOwned>(&mut self) -> Result<Option<T>> {
+        let end = match self.value_end()? {
+            Some(end) => end,
+            None => return Ok(None),
+        };
+        let value = &self.buf[self.pos..end];
+        let value =
+            serde_json::from_slice(value).map_err(|err| Error::decode(&self.url, value, err))?;
+        self.pos = end;
+        Ok(Some(value))
+    }
+
+    /// Find the end of the JSON value starting at the current position. Only
+    /// the structure is tracked here, the value itself is validated when it
+    /// is deserialized.
+    fn value_end(&mut self) -> Result<Option<usize>> {
+        let mut scan = self.scan.take().unwrap_or_default();
+        while let Some(byte) = self.buf.get(self.pos + scan.len).copied() {
+            let index = self.pos + scan.len;
+            scan.len += 1;
+            if scan.in_string {
+                if scan.escaped {
+                    scan.escaped = false;
+                } else if byte == b'\\' {
+                    scan.escaped = true;
+                } else if byte == b'"' {
+                    scan.in_string = false;
+                    if scan.depth == 0 {
+                        return Ok(Some(index + 1));
+                    }
+                }
+                continue;
+            }
+            match byte {
+                b'"' => scan.in_string = true,
+                b'{' | b'[' => scan.depth += 1,
+                b'}' | b']' if scan.depth > 0 => {
+                    scan.depth -= 1;
+                    if scan.depth == 0 {
+                        return Ok(Some(index + 1));
+                    }
+                }
+                // The end of a number or literal
+                b'}' | b']' | b',' if scan.depth == 0 => return Ok(Some(index)),
+                byte if byte.is_ascii_whitespace() && scan.depth == 0 => return Ok(Some(index)),
+                _ => {}
+            }
+        }
+        if !self.eof {
+            self.scan = Some(scan);
+            return Ok(None);
+        }
+        if scan.depth == 0 && !scan.in_string {
+            return Ok(Some(self.buf.len()));
+        }
+        Err(self.syntax("unexpected end of body"))
+    }
+
+    fn syntax(&self, msg: &str) -> Error {
+        let err = serde_json::Error::custom(format!("{} at byte {} of page", msg, self.pos));
+        Error::decode(&self.url, &self.buf[self.pos..], err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::{json, Value};
+
+    /// Decode the given body, split into chunks of the given size.
+    fn decode(body: &str, chunk_size: usize) -> Result<(Vec<Value>, Option<String>)> {
+        let mut decoder = PageDecoder::new("/test");
+        let mut chunks = body.as_bytes().chunks(chunk_size);
+        let mut entries = vec![];
+        loop {
+            match decoder.next()? {
+                Step::Entry(entry) => entries.push(entry),
+                Step::Done(cursor) => return Ok((entries, cursor)),
+                Step::NeedMore => match chunks.next() {
+                    Some(chunk) => decoder.push(chunk),
+                    None => decoder.finish(),
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn chunks() {
+        let body = r#" {"data": [{"a": "x\"]}"}, [1, {}], 2.5, "s", null, true], "cursor": "c" } "#;
+        for chunk_size in 1..=body.len() {
+            let (entries, cursor) = decode(body, chunk_size).expect("page");
+            assert_eq!(
+                entries,
+                [
+                    json!({"a": "x\"]}"}),
+                    json!([1, {}]),
+                    json!(2.5),
+                    json!("s"),
+                    json!(null),
+                    json!(true)
+                ]
+            );
+            assert_eq!(cursor.as_deref(), Some("c"));
+        }
+    }
+
+    #[test]
+    fn envelope() {
+        let (entries, cursor) =
+            decode(r#"{"cursor": null, "extra": {"x": [1]}, "data": [1]}"#, 3).expect("page");
+        assert_eq!((entries, cursor), (vec![json!(1)], None));
+        let (entries, cursor) = decode(r#"{"data":[]}"#, 4).expect("page");
+        assert_eq!((entries, cursor), (vec![], None));
+    }
+
+    #[test]
+    fn invalid() {
+        for body in [
+            r#"{"cursor": "c"}"#,
+            r#"{"data": [1,]}"#,
+            r#"{"data": [1 2]}"#,
+            r#"{"data": [1]"#,
+            r#"{"data": {}}"#,
+            r#"{"data": [tru]}"#,
+            r#"[]"#,
+        ] {
+            assert!(
+                matches!(decode(body, 2), Err(Error::Decode { .. })),
+                "{}",
+                body
+            );
+        }
+    }
+}
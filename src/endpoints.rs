@@ -0,0 +1,288 @@
+use crate::{
+    models::Height,
+    transport::{Request, Transport},
+    Data, NO_QUERY,
+};
+use futures::future;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The default time an endpoint is skipped after it failed.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+/// The default number of blocks an endpoint may lag behind the highest
+/// endpoint before a health check considers it unhealthy.
+const DEFAULT_MAX_LAG: u64 = 10;
+
+/// An ordered list of API base URLs to fail over between.
+///
+/// Requests go to the first healthy endpoint in the list. When a request
+/// fails with a transient error the endpoint is marked unhealthy for a
+/// cooldown period, and the request is sent to the next healthy endpoint
+/// right away. Since the cursor of a paged request is part of each page
+/// request, streams continue on the next endpoint where they left off.
+///
+/// `Client::check_endpoints` compares the block height of all endpoints and
+/// marks endpoints that can not be reached or lag behind as unhealthy. Call
+/// it periodically to move traffic away from stale endpoints.
+///
+/// The list is cheap to clone and all clones share the same health state.
+/// This includes the endpoints of a `Client` and all clones of that client.
+#[derive(Clone, Debug)]
+pub struct Endpoints {
+    urls: Arc<Vec<String>>,
+    cooldown: Duration,
+    max_lag: u64,
+    down_until: Arc<Mutex<Vec<Option<Instant>>>>,
+}
+
+/// The result of a health check for one endpoint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EndpointStatus {
+    /// The base URL of the endpoint
+    pub url: String,
+    /// The block height reported by the endpoint, if it could be reached
+    pub height: Option<u64>,
+    /// Whether the endpoint is considered healthy after the check
+    pub healthy: bool,
+}
+
+impl Endpoints {
+    /// Create a list of endpoints from base URLs in order of preference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no base URL is given.
+    pub fn new<I, S>(urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let urls: Vec<String> = urls.into_iter().map(Into::into).collect();
+        assert!(!urls.is_empty(), "at least one base URL is required");
+        Self {
+            down_until: Arc::new(Mutex::new(vec![None; urls.len()])),
+            urls: Arc::new(urls),
+            cooldown: DEFAULT_COOLDOWN,
+            max_lag: DEFAULT_MAX_LAG,
+        }
+    }
+
+    /// Set how long an endpoint is skipped after it failed.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Set the number of blocks an endpoint may lag behind the highest
+    /// endpoint before a health check considers it unhealthy.
+    pub fn max_lag(mut self, max_lag: u64) -> Self {
+        self.max_lag = max_lag;
+        self
+    }
+
+    /// The base URLs in order of preference.
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    /// The base URL requests are currently sent to. This is the first healthy
+    /// endpoint, or the one that recovers first when none is healthy.
+    pub fn current(&self) -> &str {
+        let now = Instant::now();
+        let down_until = self.down_until.lock().expect("endpoint state");
+        let index = down_until
+            .iter()
+            .position(|until| is_up(until, now))
+            .or_else(|| (0..down_until.len()).min_by_key(|index| down_until[*index].unwrap_or(now)))
+            .unwrap_or(0);
+        &self.urls[index]
+    }
+
+    /// Whether the endpoint with the given base URL is currently healthy.
+    pub fn is_healthy(&self, url: &str) -> bool {
+        let now = Instant::now();
+        let down_until = self.down_until.lock().expect("endpoint state");
+        self.urls
+            .iter()
+            .zip(down_until.iter())
+            .any(|(u, until)| u == url && is_up(until, now))
+    }
+
+    /// The number of endpoints that are currently healthy.
+    pub(crate) fn healthy_count(&self) -> usize {
+        let now = Instant::now();
+        self.down_until
+            .lock()
+            .expect("endpoint state")
+            .iter()
+            .filter(|until| is_up(until, now))
+            .count()
+    }
+
+    /// Mark the endpoint with the given base URL as unhealthy for the
+    /// cooldown period.
+    pub fn mark_down(&self, url: &str) {
+        self.set_down(url, Some(Instant::now() + self.cooldown));
+    }
+
+    /// Mark the endpoint with the given base URL as healthy.
+    pub fn mark_up(&self, url: &str) {
+        self.set_down(url, None);
+    }
+
+    fn set_down(&self, url: &str, until: Option<Instant>) {
+        let mut down_until = self.down_until.lock().expect("endpoint state");
+        for (index, u) in self.urls.iter().enumerate() {
+            if u == url {
+                down_until[index] = until;
+            }
+        }
+    }
+
+    /// Check the block height of all endpoints through the given transport,
+    /// and update their health accordingly.
+    pub(crate) async fn check(&self, transport: &dyn Transport) -> Vec<EndpointStatus> {
+        let heights = future::join_all(self.urls.iter().map(|url| height(transport, url))).await;
+        let max_height = heights.iter().flatten().max().copied();
+        self.urls
+            .iter()
+            .zip(heights)
+            .map(|(url, height)| {
+                let healthy = match (height, max_height) {
+                    (Some(height), Some(max_height)) => height + self.max_lag >= max_height,
+                    _ => false,
+                };
+                if healthy {
+                    self.mark_up(url);
+                } else {
+                    self.mark_down(url);
+                }
+                EndpointStatus {
+                    url: url.clone(),
+                    height,
+                    healthy,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether an endpoint that is down until the given time is up again.
+fn is_up(down_until: &Option<Instant>, now: Instant) -> bool {
+    match down_until {
+        Some(until) => *until <= now,
+        None => true,
+    }
+}
+
+async fn height(transport: &dyn Transport, base_url: &str) -> Option<u64> {
+    let request = Request::get(base_url, "/blocks/height", NO_QUERY).ok()?;
+    let response = transport.send(request).await.ok()?;
+    if !response.status.is_success() {
+        return None;
+    }
+    let data: Data<Height> = crate::decode(response).await.ok()?;
+    Some(data.data.height)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        transport::{MemoryTransport, Response},
+        Client, IntoVec, Result, RetryPolicy,
+    };
+    use async_trait::async_trait;
+    use futures::StreamExt;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+    use tokio::test;
+
+    /// A transport that routes requests to a memory transport per base URL,
+    /// and answers with a server error for unknown base URLs.
+    #[derive(Clone, Debug, Default)]
+    struct Hosts(Arc<Mutex<HashMap<String, MemoryTransport>>>);
+
+    impl Hosts {
+        fn with(self, base_url: &str, host: MemoryTransport) -> Self {
+            self.0.lock().unwrap().insert(base_url.to_string(), host);
+            self
+        }
+    }
+
+    #[async_trait]
+    impl Transport for Hosts {
+        async fn send(&self, request: Request) -> Result<Response> {
+            let host = self.0.lock().unwrap().get(&request.base_url).cloned();
+            match host {
+                Some(host) => host.send(request).await,
+                None => Ok(Response::new(
+                    reqwest::StatusCode::SERVICE_UNAVAILABLE,
+                    &request.url(),
+                    "",
+                )),
+            }
+        }
+    }
+
+    fn height(height: u64) -> MemoryTransport {
+        MemoryTransport::new().with_json("/blocks/height", json!({"data": {"height": height}}))
+    }
+
+    fn pages(name: &str) -> MemoryTransport {
+        MemoryTransport::new()
+            .with_json("/ouis", json!({"data": [1], "cursor": "a"}))
+            .with_json_query(
+                "/ouis",
+                &[("cursor", "a")],
+                json!({"data": [2], "cursor": "b"}),
+            )
+            .with_json_query("/ouis", &[("cursor", "b")], json!({ "data": [name] }))
+    }
+
+    #[test]
+    async fn check() {
+        let hosts = Hosts::default()
+            .with("http://a", height(100))
+            .with("http://b", height(200))
+            .with("http://c", height(195));
+        let endpoints = Endpoints::new(["http://a", "http://b", "http://c", "http://d"]);
+        let status = endpoints.check(&hosts).await;
+        let healthy: Vec<_> = status.iter().map(|s| s.healthy).collect();
+        assert_eq!(healthy, [false, true, true, false]);
+        assert_eq!(status[0].height, Some(100));
+        assert_eq!(status[3].height, None);
+        assert_eq!(endpoints.current(), "http://b");
+    }
+
+    #[test]
+    async fn fail_over() {
+        let primary = pages("primary");
+        let secondary = pages("secondary");
+        let hosts = Hosts::default()
+            .with("http://primary", primary.clone())
+            .with("http://secondary", secondary.clone());
+        let endpoints = Endpoints::new(["http://primary", "http://secondary"]);
+        let client = Client::builder()
+            .endpoints(endpoints.clone())
+            .transport(hosts.clone())
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .expect("client");
+
+        let mut pages = client.fetch_pages::<Value, _>("/ouis", NO_QUERY, None);
+        let first = pages.next().await.expect("page").expect("page");
+        assert_eq!(first.items, [json!(1)]);
+        hosts.0.lock().unwrap().remove("http://primary");
+        let rest = pages.into_vec().await.expect("pages");
+        assert_eq!(rest[1].items, [json!("secondary")]);
+
+        assert!(!endpoints.is_healthy("http://primary"));
+        assert_eq!(endpoints.current(), "http://secondary");
+        assert_eq!(primary.requests().len(), 1);
+        let queries: Vec<_> = secondary.requests().into_iter().map(|r| r.query).collect();
+        assert_eq!(queries, ["cursor=a", "cursor=b"]);
+    }
+}
@@ -1,16 +1,52 @@
+use reqwest::StatusCode;
+use std::time::Duration;
 use thiserror::Error;
 pub type Result<T = ()> = std::result::Result<T, Error>;
 
+/// The maximum number of characters of a response body kept in a `Decode`
+/// error.
+const BODY_SNIPPET_LEN: usize = 256;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("request error")]
     Request(#[from] reqwest::Error),
+    #[error("not found: {url}")]
+    NotFound { url: String },
+    #[error("rate limited: {url}")]
+    RateLimited {
+        url: String,
+        /// The delay the API asked for before issuing another request
+        retry_after: Option<Duration>,
+    },
+    #[error("http error {status}: {url}")]
+    Http {
+        status: StatusCode,
+        url: String,
+        body: String,
+        /// The delay the API asked for before issuing another request
+        retry_after: Option<Duration>,
+    },
+    #[error("decode error for {url}: {serde_error}")]
+    Decode {
+        url: String,
+        /// The start of the response body that failed to decode
+        body_snippet: String,
+        #[source]
+        serde_error: serde_json::Error,
+    },
     #[error("unexpected value")]
     Value(serde_json::Value),
     #[error("invalid decimals in {0}, only 8 allowed")]
     Decimals(String),
     #[error("unexpected or invalid number {0}")]
     Number(String),
+    #[error("invalid header {0}")]
+    Header(String),
+    #[error("request encoding error: {0}")]
+    Encode(String),
+    #[error("io error")]
+    Io(#[from] std::io::Error),
 }
 
 impl Error {
@@ -25,4 +61,119 @@ impl Error {
     pub fn number(value: &str) -> Self {
         Self::Number(value.to_string())
     }
+
+    pub fn header(name: &str) -> Self {
+        Self::Header(name.to_string())
+    }
+
+    pub fn encode<E: std::fmt::Display>(err: E) -> Self {
+        Self::Encode(err.to_string())
+    }
+
+    pub fn decode(url: &str, body: &[u8], serde_error: serde_json::Error) -> Self {
+        Self::Decode {
+            url: url.to_string(),
+            body_snippet: String::from_utf8_lossy(body)
+                .chars()
+                .take(BODY_SNIPPET_LEN)
+                .collect(),
+            serde_error,
+        }
+    }
+
+    /// Construct an error from a response with an unsuccessful status.
+    pub(crate) async fn from_response(response: crate::transport::Response) -> Self {
+        let url = response.url.clone();
+        match response.status {
+            StatusCode::NOT_FOUND => Self::NotFound { url },
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited {
+                url,
+                retry_after: crate::retry::retry_after(&response.headers),
+            },
+            status => {
+                let retry_after = crate::retry::retry_after(&response.headers);
+                Self::Http {
+                    status,
+                    url,
+                    body: response.text().await.unwrap_or_default(),
+                    retry_after,
+                }
+            }
+        }
+    }
+
+    /// Whether the request that caused this error may succeed when retried.
+    /// This is the case when the API rate limits requests, has a server
+    /// error, or can not be reached in time.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } => true,
+            Self::Http { status, .. } => status.is_server_error(),
+            Self::Request(err) => err.is_timeout() || err.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// Whether the requested resource does not exist.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFound { .. })
+    }
+
+    /// The delay the API asked for before issuing another request, if any.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } | Self::Http { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// The HTTP status of the response that caused this error, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::NotFound { .. } => Some(StatusCode::NOT_FOUND),
+            Self::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            Self::Http { status, .. } => Some(*status),
+            Self::Request(err) => err.status(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn retryable() {
+        let url = "https://api.helium.io/v1/blocks/height".to_string();
+        let http = |status| Error::Http {
+            status,
+            url: url.clone(),
+            body: String::new(),
+            retry_after: None,
+        };
+        assert!(http(StatusCode::SERVICE_UNAVAILABLE).is_retryable());
+        assert!(!http(StatusCode::BAD_REQUEST).is_retryable());
+        assert!(Error::RateLimited {
+            url: url.clone(),
+            retry_after: None
+        }
+        .is_retryable());
+        let not_found = Error::NotFound { url };
+        assert!(not_found.is_not_found());
+        assert!(!not_found.is_retryable());
+        assert_eq!(not_found.status(), Some(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn decode_snippet() {
+        let body = "x".repeat(BODY_SNIPPET_LEN * 2);
+        let serde_error = serde_json::from_str::<u64>(&body).unwrap_err();
+        match Error::decode("/blocks/height", body.as_bytes(), serde_error) {
+            Error::Decode { body_snippet, .. } => {
+                assert_eq!(body_snippet.len(), BODY_SNIPPET_LEN)
+            }
+            _ => panic!(),
+        }
+    }
 }
@@ -0,0 +1,205 @@
+//! Record and replay API interactions for offline tests.
+//!
+//! A `RecordingTransport` forwards requests to another transport and stores
+//! every request with its response in a fixture file. The fixture can later
+//! be replayed with `Fixture::replay`, which serves the recorded responses
+//! from memory, including every page of a paged request.
+//!
+//! ```no_run
+//! use helium_api::{blocks, fixture::Fixture, Client};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> helium_api::Result {
+//! let transport = Fixture::load("fixtures/blocks/height.json")?.replay()?;
+//! let client = Client::builder().transport(transport).build()?;
+//! let height = blocks::height(&client).await?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::{
+    transport::{MemoryTransport, Request, Response, Transport},
+    Error, Result,
+};
+use async_trait::async_trait;
+use reqwest::{header::HeaderMap, Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// A recorded request and the response it received.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The request method
+    pub method: String,
+    /// The API path relative to the base URL
+    pub path: String,
+    /// The URL encoded query string
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub query: String,
+    /// The response status
+    pub status: u16,
+    /// The response body if it is JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<serde_json::Value>,
+    /// The response body if it is not JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_text: Option<String>,
+}
+
+/// The recorded interactions stored in a fixture file, in the order they
+/// took place.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Fixture {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Fixture {
+    /// Load a fixture from the given file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let data = fs::read(path)?;
+        serde_json::from_slice(&data)
+            .map_err(|err| Error::decode(&path.display().to_string(), &data, err))
+    }
+
+    /// Store the fixture in the given file, creating parent directories as
+    /// needed.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut data = serde_json::to_vec_pretty(self).map_err(Error::encode)?;
+        data.push(b'\n');
+        fs::write(path, data)?;
+        Ok(())
+    }
+
+    /// Create a transport that serves the recorded responses. Requests are
+    /// matched on method, path and query. When the same request was recorded
+    /// more than once the responses are served in the recorded order.
+    pub fn replay(&self) -> Result<MemoryTransport> {
+        let transport = MemoryTransport::new();
+        for interaction in &self.interactions {
+            let method =
+                Method::from_bytes(interaction.method.as_bytes()).map_err(Error::encode)?;
+            let status = StatusCode::from_u16(interaction.status).map_err(Error::encode)?;
+            let query: Vec<(String, String)> =
+                serde_urlencoded::from_str(&interaction.query).map_err(Error::encode)?;
+            let body = match (&interaction.response, &interaction.response_text) {
+                (Some(json), _) => json.to_string(),
+                (None, Some(text)) => text.clone(),
+                (None, None) => String::new(),
+            };
+            transport.insert(
+                method,
+                &interaction.path,
+                Some(&query),
+                status,
+                HeaderMap::new(),
+                body,
+            )?;
+        }
+        Ok(transport)
+    }
+}
+
+/// A transport that records all requests and their responses to a fixture
+/// file. The file is rewritten after every request, so a partially completed
+/// run still leaves a usable fixture behind.
+#[derive(Clone, Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    path: PathBuf,
+    fixture: Arc<Mutex<Fixture>>,
+}
+
+impl RecordingTransport {
+    /// Record the requests sent through the given transport to the fixture
+    /// file at `path`. Any existing fixture at that path is replaced.
+    pub fn new<T, P>(inner: T, path: P) -> Self
+    where
+        T: Transport + 'static,
+        P: Into<PathBuf>,
+    {
+        Self {
+            inner: Arc::new(inner),
+            path: path.into(),
+            fixture: Arc::new(Mutex::new(Fixture::default())),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn send(&self, request: Request) -> Result<Response> {
+        let method = request.method.to_string();
+        let path = request.path.clone();
+        let query = request.query.clone();
+        let response = self.inner.send(request).await?;
+        let status = response.status;
+        let headers = response.headers.clone();
+        let url = response.url.clone();
+        let body = response.bytes().await?;
+        let (json, text) = match serde_json::from_slice(&body) {
+            Ok(json) => (Some(json), None),
+            Err(_) if body.is_empty() => (None, None),
+            Err(_) => (None, Some(String::from_utf8_lossy(&body).into_owned())),
+        };
+        let fixture = {
+            let mut fixture = self.fixture.lock().expect("recording fixture");
+            fixture.interactions.push(Interaction {
+                method,
+                path,
+                query,
+                status: status.as_u16(),
+                response: json,
+                response_text: text,
+            });
+            fixture.clone()
+        };
+        fixture.save(&self.path)?;
+        let mut result = Response::new(status, &url, body);
+        result.headers = headers;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use tokio::test;
+
+    #[test]
+    async fn record_replay() {
+        let path =
+            std::env::temp_dir().join(format!("helium-api-fixture-{}.json", std::process::id()));
+        let source = MemoryTransport::new()
+            .with_json("/ouis", json!({"data": [1], "cursor": "a"}))
+            .with_json_query("/ouis", &[("cursor", "a")], json!({"data": [2]}));
+        let recorder = RecordingTransport::new(source, &path);
+        for query in [vec![], vec![("cursor", "a")]] {
+            let request = Request::get("http://localhost", "/ouis", &query).expect("request");
+            recorder.send(request).await.expect("response");
+        }
+
+        let fixture = Fixture::load(&path).expect("fixture");
+        fs::remove_file(&path).expect("remove fixture");
+        assert_eq!(fixture.interactions.len(), 2);
+        assert_eq!(fixture.interactions[1].query, "cursor=a");
+
+        let replay = fixture.replay().expect("replay");
+        let request =
+            Request::get("http://localhost", "/ouis", &[("cursor", "a")]).expect("request");
+        let body = replay.send(request).await.expect("response").text().await;
+        assert_eq!(body.expect("body"), r#"{"data":[2]}"#);
+        let request =
+            Request::get("http://localhost", "/ouis", &[("cursor", "b")]).expect("request");
+        let response = replay.send(request).await.expect("response");
+        assert_eq!(response.status, StatusCode::NOT_FOUND);
+    }
+}
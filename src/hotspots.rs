@@ -1,10 +1,22 @@
-use crate::{models::Hotspot, *};
+use crate::{
+    models::{
+        transactions::TransactionEnvelope, Bucket, Hotspot, QueryActivity, QueryRewardSum,
+        QueryTimeRange, Reward, RewardSums, Witness,
+    },
+    *,
+};
 
 /// Get all known hotspots
 pub fn all(client: &Client) -> Stream<Hotspot> {
     client.fetch_stream("/hotspots", NO_QUERY)
 }
 
+/// Get all known hotspots as pages, starting after the page for the given
+/// cursor if any
+pub fn all_pages(client: &Client, cursor: Option<&str>) -> Stream<Page<Hotspot>> {
+    client.fetch_pages("/hotspots", NO_QUERY, cursor)
+}
+
 /// Get a specific hotspot by its address
 pub async fn get(client: &Client, address: &str) -> Result<Hotspot> {
     client
@@ -12,9 +24,185 @@ pub async fn get(client: &Client, address: &str) -> Result<Hotspot> {
         .await
 }
 
+/// Get the hotspots for the given addresses, with up to `concurrency` requests
+/// in flight, or one if `concurrency` is zero. Each result is returned with its
+/// address, in the order given by `order`, and a failed lookup does not end the
+/// stream.
+pub fn get_many<I, S>(
+    client: &Client,
+    addresses: I,
+    concurrency: usize,
+    order: Order,
+) -> Batch<Hotspot>
+where
+    I: IntoIterator<Item = S>,
+    I::IntoIter: Send + 'static,
+    S: Into<String>,
+{
+    client.get_many("/hotspots", addresses, concurrency, order)
+}
+
+/// Get the hotspots within `distance` meters of the given location, with
+/// their distance to it
+pub fn near(client: &Client, lat: f64, lon: f64, distance: u32) -> Stream<Hotspot> {
+    client.fetch_stream(
+        "/hotspots/location/distance",
+        &[
+            ("lat", lat.to_string()),
+            ("lon", lon.to_string()),
+            ("distance", distance.to_string()),
+        ],
+    )
+}
+
+/// Get the hotspots near a location as pages, starting after the page for
+/// the given cursor if any. The location is ignored when resuming from a
+/// cursor.
+pub fn near_pages(
+    client: &Client,
+    lat: f64,
+    lon: f64,
+    distance: u32,
+    cursor: Option<&str>,
+) -> Stream<Page<Hotspot>> {
+    client.fetch_pages(
+        "/hotspots/location/distance",
+        &[
+            ("lat", lat.to_string()),
+            ("lon", lon.to_string()),
+            ("distance", distance.to_string()),
+        ],
+        cursor,
+    )
+}
+
+/// Get the hotspots within the box with the given south west and north east
+/// corners
+pub fn in_box(client: &Client, swlat: f64, swlon: f64, nelat: f64, nelon: f64) -> Stream<Hotspot> {
+    client.fetch_stream(
+        "/hotspots/location/box",
+        &[
+            ("swlat", swlat),
+            ("swlon", swlon),
+            ("nelat", nelat),
+            ("nelon", nelon),
+        ],
+    )
+}
+
+/// Get the hotspots within a box as pages, starting after the page for the
+/// given cursor if any. The box is ignored when resuming from a cursor.
+pub fn in_box_pages(
+    client: &Client,
+    swlat: f64,
+    swlon: f64,
+    nelat: f64,
+    nelon: f64,
+    cursor: Option<&str>,
+) -> Stream<Page<Hotspot>> {
+    client.fetch_pages(
+        "/hotspots/location/box",
+        &[
+            ("swlat", swlat),
+            ("swlon", swlon),
+            ("nelat", nelat),
+            ("nelon", nelon),
+        ],
+        cursor,
+    )
+}
+
+/// Get the hotspots located in the given h3 hex. The hex can be of any
+/// resolution.
+pub fn in_hex(client: &Client, h3_index: &str) -> Stream<Hotspot> {
+    client.fetch_stream(&format!("/hotspots/hex/{}", h3_index), NO_QUERY)
+}
+
+/// Get the hotspots located in an h3 hex as pages, starting after the page
+/// for the given cursor if any
+pub fn in_hex_pages(
+    client: &Client,
+    h3_index: &str,
+    cursor: Option<&str>,
+) -> Stream<Page<Hotspot>> {
+    client.fetch_pages(&format!("/hotspots/hex/{}", h3_index), NO_QUERY, cursor)
+}
+
+/// Get the hotspots that witness the given hotspot, with their witness
+/// reports for it
+pub fn witnesses(client: &Client, address: &str) -> Stream<Witness> {
+    client.fetch_stream(&format!("/hotspots/{}/witnesses", address), NO_QUERY)
+}
+
+/// Get the hotspots the given hotspot witnesses, with its witness reports
+/// for them
+pub fn witnessed(client: &Client, address: &str) -> Stream<Witness> {
+    client.fetch_stream(&format!("/hotspots/{}/witnessed", address), NO_QUERY)
+}
+
+/// Fetches transactions that indicate activity for a hotspot, like its
+/// location assertions, proof of coverage and data transfers. Only
+/// transactions of the types in `filter_types` are returned, if any.
+pub fn activity(
+    client: &Client,
+    address: &str,
+    query: &QueryActivity,
+) -> Stream<TransactionEnvelope> {
+    client.fetch_stream(&format!("/hotspots/{}/activity", address), query)
+}
+
+/// Fetches hotspot activity as pages, starting after the page for the given
+/// cursor if any. The query is ignored when resuming from a cursor.
+pub fn activity_pages(
+    client: &Client,
+    address: &str,
+    query: &QueryActivity,
+    cursor: Option<&str>,
+) -> Stream<Page<TransactionEnvelope>> {
+    client.fetch_pages(&format!("/hotspots/{}/activity", address), query, cursor)
+}
+
+/// Get rewards for a hotspot
+///
+/// Returns the rewards earned by the given hotspot per reward block, for a
+/// given timeframe. The block that contains the max_time timestamp is
+/// excluded from the result.
+pub fn rewards(client: &Client, address: &str, query: &QueryTimeRange) -> Stream<Reward> {
+    client.fetch_stream(&format!("/hotspots/{}/rewards", address), query)
+}
+
+/// Get rewards for a hotspot as pages, starting after the page for the
+/// given cursor if any. The query is ignored when resuming from a cursor.
+pub fn rewards_pages(
+    client: &Client,
+    address: &str,
+    query: &QueryTimeRange,
+    cursor: Option<&str>,
+) -> Stream<Page<Reward>> {
+    client.fetch_pages(&format!("/hotspots/{}/rewards", address), query, cursor)
+}
+
+/// Get the sum of the rewards for a hotspot in the given timeframe, or the
+/// sums for each bucket of the given size when a bucket is given
+pub async fn rewards_sum(
+    client: &Client,
+    address: &str,
+    query: &QueryTimeRange,
+    bucket: Option<Bucket>,
+) -> Result<RewardSums> {
+    let query = QueryRewardSum {
+        range: query,
+        bucket,
+    };
+    client
+        .fetch(&format!("/hotspots/{}/rewards/sum", address), &query)
+        .await
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use models::{transactions::TransactionType, Hnt};
     use tokio::test;
 
     #[test]
@@ -42,4 +230,166 @@ mod test {
             "112vvSrNAwJRSmR54aqFLEhbr6cy6T4Ufuja4VWVrxvkUAUxL2yG"
         );
     }
+
+    #[test]
+    async fn activity() {
+        let client = get_test_client();
+        let query = QueryActivity {
+            min_time: Some("2022-06-01T00:00:00Z".into()),
+            max_time: Some("2022-06-17T00:00:00Z".into()),
+            filter_types: vec![
+                TransactionType::AssertLocationV2,
+                TransactionType::PocRequestV1,
+            ],
+        };
+        let txns = hotspots::activity(
+            &client,
+            "112vvSrNAwJRSmR54aqFLEhbr6cy6T4Ufuja4VWVrxvkUAUxL2yG",
+            &query,
+        )
+        .into_vec()
+        .await
+        .expect("activity");
+        let types: Vec<_> = txns.iter().map(|txn| txn.transaction_type()).collect();
+        assert_eq!(
+            types,
+            [
+                Some(TransactionType::PocRequestV1),
+                Some(TransactionType::AssertLocationV2)
+            ]
+        );
+    }
+
+    #[test]
+    async fn rewards() {
+        let client = get_test_client();
+        let query = QueryTimeRange {
+            min_time: "2022-06-01T00:00:00Z".into(),
+            max_time: "2022-06-02T00:00:00Z".into(),
+        };
+        let rewards = hotspots::rewards(
+            &client,
+            "112vvSrNAwJRSmR54aqFLEhbr6cy6T4Ufuja4VWVrxvkUAUxL2yG",
+            &query,
+        )
+        .into_vec()
+        .await
+        .expect("rewards");
+        assert_eq!(rewards.len(), 3);
+        assert_eq!(rewards[0].amount, Hnt::from(1804127));
+    }
+
+    #[test]
+    async fn rewards_sum() {
+        let client = get_test_client();
+        let query = QueryTimeRange {
+            min_time: "2022-06-01T00:00:00Z".into(),
+            max_time: "2022-06-02T00:00:00Z".into(),
+        };
+        let sum = hotspots::rewards_sum(
+            &client,
+            "112vvSrNAwJRSmR54aqFLEhbr6cy6T4Ufuja4VWVrxvkUAUxL2yG",
+            &query,
+            None,
+        )
+        .await
+        .expect("rewards sum");
+        if let RewardSums::Total(total) = sum {
+            assert_eq!(total.sum, Hnt::from(5299356));
+            assert_eq!(total.max, Hnt::from(2533014));
+            assert_eq!(total.stddev, Hnt::from(647815));
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    async fn rewards_buckets() {
+        let client = get_test_client();
+        let query = QueryTimeRange {
+            min_time: "2022-06-01T00:00:00Z".into(),
+            max_time: "2022-06-03T00:00:00Z".into(),
+        };
+        let sums = hotspots::rewards_sum(
+            &client,
+            "112vvSrNAwJRSmR54aqFLEhbr6cy6T4Ufuja4VWVrxvkUAUxL2yG",
+            &query,
+            Some(Bucket::Day),
+        )
+        .await
+        .expect("rewards buckets");
+        if let RewardSums::Buckets(buckets) = sums {
+            let totals: Vec<_> = buckets.iter().map(|bucket| bucket.rewards.sum).collect();
+            assert_eq!(totals, [Hnt::from(4944663), Hnt::from(5299356)]);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    async fn witnesses() {
+        let client = get_test_client();
+        let witnesses = hotspots::witnesses(
+            &client,
+            "112vvSrNAwJRSmR54aqFLEhbr6cy6T4Ufuja4VWVrxvkUAUxL2yG",
+        )
+        .into_vec()
+        .await
+        .expect("witnesses");
+        assert_eq!(witnesses.len(), 2);
+        assert_eq!(witnesses[0].name.as_deref(), Some("rich-carrot-walrus"));
+        let info = witnesses[0].witness_info.as_ref().expect("witness info");
+        assert_eq!(info.histogram[&-116], 11);
+        assert_eq!(info.count(), 17);
+        assert!(info.first_time < info.recent_time);
+    }
+
+    #[test]
+    async fn witnessed() {
+        let client = get_test_client();
+        let witnessed = hotspots::witnessed(
+            &client,
+            "112vvSrNAwJRSmR54aqFLEhbr6cy6T4Ufuja4VWVrxvkUAUxL2yG",
+        )
+        .into_vec()
+        .await
+        .expect("witnessed");
+        assert_eq!(witnessed.len(), 1);
+        assert!(witnessed[0].witness_info.is_none());
+    }
+
+    #[test]
+    async fn near() {
+        let client = get_test_client();
+        let hotspots = hotspots::near(&client, 38.722271, -9.139328, 1000)
+            .into_vec()
+            .await
+            .expect("hotspots");
+        let distances: Vec<_> = hotspots.iter().map(|hotspot| hotspot.distance).collect();
+        assert_eq!(distances, [Some(0.0), Some(653.57), Some(735.817)]);
+    }
+
+    #[test]
+    async fn in_box() {
+        let client = get_test_client();
+        let hotspots = hotspots::in_box(&client, 38.71, -9.145, 38.725, -9.13)
+            .into_vec()
+            .await
+            .expect("hotspots");
+        assert_eq!(hotspots.len(), 2);
+        assert!(hotspots.iter().all(|hotspot| hotspot.distance.is_none()));
+    }
+
+    #[test]
+    async fn in_hex() {
+        let client = get_test_client();
+        let hotspots = hotspots::in_hex(&client, "8838a162e3fffff")
+            .into_vec()
+            .await
+            .expect("hotspots");
+        assert_eq!(
+            hotspots[0].address,
+            "112vvSrNAwJRSmR54aqFLEhbr6cy6T4Ufuja4VWVrxvkUAUxL2yG"
+        );
+    }
 }
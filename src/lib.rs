@@ -1,9 +1,12 @@
 use async_trait::async_trait;
+use decoder::{PageChunk, PageDecoder, Step};
 use futures::{
-    future, stream, Future as StdFuture, FutureExt, Stream as StdStream, StreamExt, TryFutureExt,
+    stream, Future as StdFuture, FutureExt, Stream as StdStream, StreamExt, TryStreamExt,
 };
+use observer::{RequestObserver, RequestSpan};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{pin::Pin, time::Duration};
+use std::{pin::Pin, sync::Arc, time::Duration};
+use transport::Transport;
 
 /// A type alias for `Future` that may return `crate::error::Error`
 pub type Future<T> = Pin<Box<dyn StdFuture<Output = Result<T>> + Send>>;
@@ -11,18 +14,40 @@ pub type Future<T> = Pin<Box<dyn StdFuture<Output = Result<T>> + Send>>;
 /// A type alias for `Stream` that may result in `crate::error::Error`
 pub type Stream<T> = Pin<Box<dyn StdStream<Item = Result<T>> + Send>>;
 
+/// A type alias for the `Stream` of results of a batch lookup, each paired
+/// with the address it was looked up for
+pub type Batch<T> = Pin<Box<dyn StdStream<Item = (String, Result<T>)> + Send>>;
+
+mod builder;
+mod cache;
+mod decoder;
+mod endpoints;
 mod error;
+pub mod observer;
+mod rate_limit;
+mod retry;
 
+pub use builder::ClientBuilder;
+pub use cache::{Cache, CacheStats};
+pub use endpoints::{EndpointStatus, Endpoints};
 pub use error::{Error, Result};
+pub use rate_limit::{RateLimitStats, RateLimiter};
+pub use retry::RetryPolicy;
 
 pub mod accounts;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod blocks;
+pub mod cities;
+pub mod fixture;
 pub mod hotspots;
+pub mod locations;
 pub mod models;
 pub mod oracle;
 pub mod ouis;
 pub mod pending_transactions;
 pub mod transactions;
+pub mod transport;
 pub mod validators;
 pub mod vars;
 
@@ -30,6 +55,10 @@ pub mod vars;
 pub const DEFAULT_TIMEOUT: u64 = 120;
 /// The default base URL if none is specified.
 pub const DEFAULT_BASE_URL: &str = "https://api.helium.io/v1";
+/// The default user agent if none is specified.
+pub const DEFAULT_USER_AGENT: &str = concat!("helium-api-rs/", env!("CARGO_PKG_VERSION"));
+/// The header used to send an API key set with `ClientBuilder::api_key`.
+pub const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
 /// A utility constant to pass an empty query slice to the various client fetch
 /// functions
 pub const NO_QUERY: &[&str; 0] = &[""; 0];
@@ -40,13 +69,107 @@ pub(crate) struct Data<T> {
     pub cursor: Option<String>,
 }
 
+/// A single page of a paged API response.
+///
+/// Paged endpoints are available as a stream of pages through the `*_pages`
+/// functions in each module. The cursor of a page can be stored and passed to
+/// the same function later to resume fetching after that page.
+#[derive(Clone, Debug)]
+pub struct Page<T> {
+    /// The entries in this page. A page may be empty while more pages are
+    /// still available.
+    pub items: Vec<T>,
+    /// The cursor for the next page, or `None` if this is the last page.
+    pub cursor: Option<String>,
+}
+
+/// The order in which the results of a batch lookup are returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    /// Return results in the order of the given addresses
+    Input,
+    /// Return results as soon as their lookup completes
+    Completion,
+}
+
+/// A decoded model together with the raw JSON it was decoded from.
+///
+/// Request a `WithRaw<T>` from `Client::get` or `Client::stream` to access
+/// fields of a response that `T` does not model:
+///
+/// ```no_run
+/// use helium_api::{models::Hotspot, Client, WithRaw, NO_QUERY};
+///
+/// # #[tokio::main]
+/// # async fn main() -> helium_api::Result {
+/// let client = Client::default();
+/// let hotspot: WithRaw<Hotspot> = client
+///     .get("/hotspots/112vvSrNAwJRSmR54aqFLEhbr6cy6T4Ufuja4VWVrxvkUAUxL2yG", NO_QUERY)
+///     .await?;
+/// println!("{} paid by {}", hotspot.address, hotspot.raw["payer"]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct WithRaw<T> {
+    /// The decoded model
+    pub value: T,
+    /// The JSON the model was decoded from
+    pub raw: serde_json::Value,
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for WithRaw<T> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = serde_json::Value::deserialize(deserializer)?;
+        let value = T::deserialize(&raw).map_err(serde::de::Error::custom)?;
+        Ok(Self { value, raw })
+    }
+}
+
+impl<T> std::ops::Deref for WithRaw<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Client {
     base_url: String,
-    client: reqwest::Client,
+    transport: Arc<dyn Transport>,
+    retry: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<Cache>,
+    endpoints: Option<Endpoints>,
+    observer: Option<Arc<dyn RequestObserver>>,
+    prefetch: usize,
+}
+
+impl Default for Client {
+    /// Create a client for the `DEFAULT_BASE_URL` using the default settings
+    /// of a `ClientBuilder`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying HTTP client can not be initialized. Use
+    /// `ClientBuilder::build` to handle that error instead.
+    fn default() -> Self {
+        ClientBuilder::default()
+            .build()
+            .expect("default client configuration")
+    }
 }
 
 impl Client {
+    /// Create a `ClientBuilder` to configure a client.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
     /// Create a new client using a given base URL and a default
     /// timeout. The library will use absoluate paths based on this
     /// base_url.
@@ -57,82 +180,399 @@ impl Client {
     /// Create a new client using a given base URL, and request
     /// timeout value.  The library will use absoluate paths based on
     /// the given base_url.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying HTTP client can not be initialized. Use
+    /// `ClientBuilder` to handle that error instead.
     pub fn new_with_timeout(base_url: String, user_agent: &str, timeout: u64) -> Self {
-        let client = reqwest::Client::builder()
-            .gzip(true)
+        ClientBuilder::new(base_url)
             .user_agent(user_agent)
             .timeout(Duration::from_secs(timeout))
             .build()
-            .unwrap();
-        Self { base_url, client }
+            .expect("client configuration")
     }
 
-    pub(crate) fn fetch_data<T, Q>(&self, path: &str, query: &Q) -> Future<Data<T>>
+    /// The base URL this client uses for requests. When the client fails
+    /// over between several endpoints this is the preferred one.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// The number of pages fetched ahead of the consumer of a paged request.
+    pub fn prefetch(&self) -> usize {
+        self.prefetch
+    }
+
+    /// The endpoints this client fails over between, if any.
+    pub fn endpoints(&self) -> Option<&Endpoints> {
+        self.endpoints.as_ref()
+    }
+
+    /// Check the block height of every endpoint of this client and mark the
+    /// ones that can not be reached, or lag behind the others, as unhealthy.
+    /// A client without failover endpoints checks its base URL.
+    pub async fn check_endpoints(&self) -> Vec<EndpointStatus> {
+        match &self.endpoints {
+            Some(endpoints) => endpoints.check(self.transport.as_ref()).await,
+            None => {
+                Endpoints::new([self.base_url.as_str()])
+                    .check(self.transport.as_ref())
+                    .await
+            }
+        }
+    }
+
+    /// The retry policy this client uses for requests.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// The rate limiter shared by this client and all its clones, if any.
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// The response cache shared by this client and all its clones, if any.
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_ref()
+    }
+
+    /// Get the `data` of an API path that is not covered by the modules of
+    /// this crate, decoded as the given type. The path is relative to the
+    /// base URL, like `/blocks/height`.
+    ///
+    /// Use `WithRaw` to keep the raw JSON next to a typed model, for example
+    /// to access fields the model does not have yet.
+    pub async fn get<T, Q>(&self, path: &str, query: &Q) -> Result<T>
     where
         T: 'static + DeserializeOwned + std::marker::Send,
         Q: Serialize + ?Sized,
     {
-        let request_url = format!("{}{}", self.base_url, path);
-        self.client
-            .get(&request_url)
-            .query(query)
-            .send()
-            .map_err(error::Error::from)
-            .and_then(|response| match response.error_for_status() {
-                Ok(result) => {
-                    let data: Future<Data<T>> = result.json().map_err(error::Error::from).boxed();
-                    data
+        self.fetch(path, query).await
+    }
+
+    /// Get the entries of all pages of a paged API path, decoded as the given
+    /// type.
+    pub fn stream<T, Q>(&self, path: &str, query: &Q) -> Stream<T>
+    where
+        T: 'static + DeserializeOwned + std::marker::Send,
+        Q: Serialize + ?Sized,
+    {
+        self.fetch_stream(path, query)
+    }
+
+    /// Get the `data` of an API path as JSON.
+    pub async fn get_json<Q>(&self, path: &str, query: &Q) -> Result<serde_json::Value>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.get(path, query).await
+    }
+
+    /// Get the entries of all pages of a paged API path as JSON.
+    pub fn stream_json<Q>(&self, path: &str, query: &Q) -> Stream<serde_json::Value>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.stream(path, query)
+    }
+
+    /// Send the given request, retrying transient failures as allowed by the
+    /// retry policy of the client. Cacheable requests are served from and
+    /// stored in the cache of the client, if any.
+    async fn execute(
+        &self,
+        request: transport::Request,
+        span: &RequestSpan,
+    ) -> Result<transport::Response> {
+        let cache = self.cache.as_ref().filter(|cache| cache.accepts(&request));
+        let cached = match cache {
+            Some(cache) => cache.get(&request).await,
+            None => None,
+        };
+        if let Some(body) = cached {
+            return Ok(transport::Response::new(
+                reqwest::StatusCode::OK,
+                &request.url(),
+                body,
+            ));
+        }
+        let response = self.send(&request, span).await?;
+        match cache {
+            Some(cache) => {
+                let status = response.status;
+                let headers = response.headers.clone();
+                let url = response.url.clone();
+                let body = response.bytes().await?;
+                cache.insert(&request, body.clone()).await;
+                let mut response = transport::Response::new(status, &url, body);
+                response.headers = headers;
+                Ok(response)
+            }
+            None => Ok(response),
+        }
+    }
+
+    /// Send the given request through the transport of the client, retrying
+    /// transient failures as allowed by the retry policy. With failover
+    /// endpoints a transient failure is first tried on each other healthy
+    /// endpoint before it counts as a failed attempt.
+    async fn send(
+        &self,
+        request: &transport::Request,
+        span: &RequestSpan,
+    ) -> Result<transport::Response> {
+        let is_post = request.method == reqwest::Method::POST;
+        let max_attempts = self.retry.attempts(is_post);
+        let mut request = request.clone();
+        let mut attempt = 1;
+        let mut failovers = 0;
+        loop {
+            if let Some(endpoints) = &self.endpoints {
+                request.base_url = endpoints.current().to_string();
+            }
+            let rate_limit_wait = match &self.rate_limiter {
+                Some(rate_limiter) => rate_limiter.acquire().await,
+                None => Duration::ZERO,
+            };
+            let started = std::time::Instant::now();
+            let err = match self.transport.send(request.clone()).await {
+                Ok(response) if response.status.is_success() => {
+                    span.attempt(
+                        &request.base_url,
+                        attempt,
+                        rate_limit_wait,
+                        started,
+                        Some(response.status),
+                        None,
+                    );
+                    return Ok(response);
+                }
+                Ok(response) => Error::from_response(response).await,
+                Err(err) => err,
+            };
+            span.attempt(
+                &request.base_url,
+                attempt,
+                rate_limit_wait,
+                started,
+                err.status(),
+                Some(&err),
+            );
+            if let Some(endpoints) = self.endpoints.as_ref().filter(|_| err.is_retryable()) {
+                endpoints.mark_down(&request.base_url);
+                if failovers + 1 < endpoints.urls().len() && endpoints.healthy_count() > 0 {
+                    failovers += 1;
+                    continue;
+                }
+            }
+            if attempt >= max_attempts || !err.is_retryable() {
+                return Err(err);
+            }
+            let delay = self.retry.delay(attempt, err.retry_after());
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Issue the given request and decode its `Data` envelope, reporting the
+    /// request to the observer of the client.
+    fn request<T>(&self, request: Result<transport::Request>) -> Future<Data<T>>
+    where
+        T: 'static + DeserializeOwned + std::marker::Send,
+    {
+        let client = self.clone();
+        let (method, path) = match &request {
+            Ok(request) => (request.method.clone(), request.path.clone()),
+            Err(_) => (reqwest::Method::GET, String::new()),
+        };
+        let mut span = RequestSpan::new(self.observer.clone(), method, &path);
+        let instrumented = span.clone();
+        instrumented
+            .instrument(async move {
+                span.start();
+                let result: Result<_> = async {
+                    let response = client.execute(request?, &span).await?;
+                    let status = response.status;
+                    let url = response.url.clone();
+                    let body = response.bytes().await?;
+                    let data: Data<T> = serde_json::from_slice(&body)
+                        .map_err(|err| Error::decode(&url, &body, err))?;
+                    Ok((status, body.len(), data))
+                }
+                .await;
+                match result {
+                    Ok((status, bytes, data)) => {
+                        let has_cursor = data.cursor.is_some();
+                        span.finish(Some(status), bytes, None, has_cursor, None);
+                        Ok(data)
+                    }
+                    Err(err) => {
+                        span.finish(err.status(), 0, None, false, Some(&err));
+                        Err(err)
+                    }
                 }
-                Err(e) => future::err(error::Error::from(e)).boxed(),
             })
             .boxed()
     }
 
+    pub(crate) fn fetch_data<T, Q>(&self, path: &str, query: &Q) -> Future<Data<T>>
+    where
+        T: 'static + DeserializeOwned + std::marker::Send,
+        Q: Serialize + ?Sized,
+    {
+        self.request(transport::Request::get(&self.base_url, path, query))
+    }
+
+    /// Fetch a single page of a paged API path, decoding its entries one at a
+    /// time as the body is received.
+    fn fetch_page_chunks<E, Q>(&self, path: &str, query: &Q) -> Stream<PageChunk<E>>
+    where
+        E: 'static + DeserializeOwned + std::marker::Send,
+        Q: Serialize + ?Sized,
+    {
+        let page = PageBody {
+            client: self.clone(),
+            request: Some(transport::Request::get(&self.base_url, path, query)),
+            status: None,
+            body: stream::empty().boxed(),
+            decoder: PageDecoder::new(path),
+            bytes: 0,
+            entries: 0,
+            done: false,
+        };
+        let span = RequestSpan::new(self.observer.clone(), reqwest::Method::GET, path);
+        stream::unfold((page, span), |(mut page, mut span)| async move {
+            if page.done {
+                return None;
+            }
+            if page.request.is_some() {
+                span.start();
+            }
+            let chunk = span.instrument(page.next(&span)).await;
+            match &chunk {
+                Ok(PageChunk::Entry(_)) => (),
+                Ok(PageChunk::End(cursor)) => {
+                    page.done = true;
+                    let has_cursor = cursor.is_some();
+                    span.finish(
+                        page.status,
+                        page.bytes,
+                        Some(page.entries),
+                        has_cursor,
+                        None,
+                    );
+                }
+                Err(err) => {
+                    page.done = true;
+                    span.finish(err.status(), page.bytes, None, false, Some(err));
+                }
+            }
+            Some((chunk, (page, span)))
+        })
+        .boxed()
+    }
+
+    /// Fetch a single page of a paged API path.
+    fn fetch_page<E, Q>(&self, path: &str, query: &Q) -> Future<Data<Vec<E>>>
+    where
+        E: 'static + DeserializeOwned + std::marker::Send,
+        Q: Serialize + ?Sized,
+    {
+        let mut chunks = self.fetch_page_chunks(path, query);
+        async move {
+            let mut data = vec![];
+            while let Some(chunk) = chunks.next().await {
+                match chunk? {
+                    PageChunk::Entry(entry) => data.push(entry),
+                    PageChunk::End(cursor) => return Ok(Data { data, cursor }),
+                }
+            }
+            Ok(Data { data, cursor: None })
+        }
+        .boxed()
+    }
+
+    /// Fetch the entries of a paged API path. Entries are decoded and
+    /// returned as the body of each page is received, so only the entry being
+    /// decoded is buffered. With a prefetch depth whole pages are fetched
+    /// ahead of the consumer instead.
     pub(crate) fn fetch_stream<E, Q>(&self, path: &str, query: &Q) -> Stream<E>
     where
         E: 'static + DeserializeOwned + std::marker::Send,
         Q: Serialize + ?Sized,
     {
-        let path = path.to_string();
-        let client = self.clone();
-        client
-            .fetch_data::<Vec<E>, _>(&path, query)
-            .map_ok(move |mut data| {
-                data.data.reverse();
-                stream::try_unfold(
-                    (data, client, path),
-                    |(mut data, client, path)| async move {
-                        match data.data.pop() {
-                            Some(entry) => Ok(Some((entry, (data, client, path)))),
-                            None => match data.cursor {
-                                Some(cursor) => {
-                                    //loop until we find next bit of data or run
-                                    // out of cursors
-                                    let mut data: Data<Vec<E>>;
-                                    let mut cursor = cursor;
-                                    loop {
-                                        data = client
-                                            .fetch_data::<Vec<E>, _>(&path, &[("cursor", &cursor)])
-                                            .await?;
-
-                                        if !data.data.is_empty() {
-                                            data.data.reverse();
-                                            let entry = data.data.pop().unwrap();
-                                            break Ok(Some((entry, (data, client, path))));
-                                        } else if data.cursor.is_none() {
-                                            break Ok(None);
-                                        }
-                                        cursor = data.cursor.unwrap();
-                                    }
-                                }
-                                None => Ok(None),
-                            },
+        if self.prefetch > 0 {
+            return self.fetch_pages(path, query, None).into_items();
+        }
+        let first = self.fetch_page_chunks::<E, _>(path, query);
+        stream::try_unfold(
+            (Some(first), self.clone(), path.to_string()),
+            |(chunks, client, path)| async move {
+                let mut chunks = match chunks {
+                    Some(chunks) => chunks,
+                    None => return Ok(None),
+                };
+                while let Some(chunk) = chunks.next().await {
+                    match chunk? {
+                        PageChunk::Entry(entry) => {
+                            return Ok(Some((entry, (Some(chunks), client, path))))
                         }
-                    },
-                )
-            })
-            .try_flatten_stream()
-            .boxed()
+                        PageChunk::End(Some(cursor)) => {
+                            chunks = client.fetch_page_chunks(&path, &[("cursor", cursor)])
+                        }
+                        PageChunk::End(None) => break,
+                    }
+                }
+                Ok(None)
+            },
+        )
+        .boxed()
+    }
+
+    /// Fetch the pages of a paged API path. When a `cursor` is given the query
+    /// is ignored, since the cursor encodes the query it was issued for.
+    ///
+    /// With a prefetch depth the pages are fetched by a background task, up to
+    /// that many pages ahead of the consumer.
+    pub(crate) fn fetch_pages<E, Q>(
+        &self,
+        path: &str,
+        query: &Q,
+        cursor: Option<&str>,
+    ) -> Stream<Page<E>>
+    where
+        E: 'static + DeserializeOwned + std::marker::Send,
+        Q: Serialize + ?Sized,
+    {
+        let first = match cursor {
+            Some(cursor) => self.fetch_page::<E, _>(path, &[("cursor", cursor)]),
+            None => self.fetch_page::<E, _>(path, query),
+        };
+        let pages = stream::try_unfold(
+            (Some(first), self.clone(), path.to_string()),
+            |(next, client, path)| async move {
+                let data = match next {
+                    Some(next) => next.await?,
+                    None => return Ok(None),
+                };
+                let following = data
+                    .cursor
+                    .as_ref()
+                    .map(|cursor| client.fetch_page(&path, &[("cursor", cursor)]));
+                let page = Page {
+                    items: data.data,
+                    cursor: data.cursor,
+                };
+                Ok(Some((page, (following, client, path))))
+            },
+        )
+        .boxed();
+        match self.prefetch {
+            0 => pages,
+            depth => prefetch(pages, depth),
+        }
     }
 
     pub(crate) async fn fetch<T, Q>(&self, path: &str, query: &Q) -> error::Result<T>
@@ -144,28 +584,150 @@ impl Client {
         Ok(result.data)
     }
 
+    /// Look up `{path}/{address}` for each of the given addresses, with up
+    /// to `concurrency` requests in flight, or one if `concurrency` is zero.
+    /// Lookups are only started as the stream is consumed. A failed lookup is
+    /// returned with its address and does not end the stream.
+    pub(crate) fn get_many<T, I, S>(
+        &self,
+        path: &str,
+        addresses: I,
+        concurrency: usize,
+        order: Order,
+    ) -> Batch<T>
+    where
+        T: 'static + DeserializeOwned + std::marker::Send,
+        I: IntoIterator<Item = S>,
+        I::IntoIter: Send + 'static,
+        S: Into<String>,
+    {
+        let client = self.clone();
+        let path = path.to_string();
+        let lookups = stream::iter(addresses.into_iter().map(move |address| {
+            let address = address.into();
+            let data = client.fetch_data::<T, _>(&format!("{}/{}", path, address), NO_QUERY);
+            async move { (address, data.await.map(|data| data.data)) }
+        }));
+        let concurrency = concurrency.max(1);
+        match order {
+            Order::Input => lookups.buffered(concurrency).boxed(),
+            Order::Completion => lookups.buffer_unordered(concurrency).boxed(),
+        }
+    }
+
     pub(crate) fn post<T, R>(&self, path: &str, json: &T) -> Future<R>
     where
         T: Serialize + ?Sized,
         R: 'static + DeserializeOwned + std::marker::Send,
     {
-        let request_url = format!("{}{}", self.base_url, path);
-        self.client
-            .post(&request_url)
-            .json(json)
-            .send()
-            .map_err(error::Error::from)
-            .and_then(|response| match response.error_for_status() {
-                Ok(result) => {
-                    let data: Future<R> = result
-                        .json()
-                        .map_err(error::Error::from)
-                        .map_ok(|v: Data<R>| v.data)
-                        .boxed();
-                    data
+        let data = self.request(transport::Request::post(&self.base_url, path, json));
+        async move { Ok(data.await?.data) }.boxed()
+    }
+}
+
+/// A page that is decoded while its body is received.
+struct PageBody<E> {
+    client: Client,
+    request: Option<Result<transport::Request>>,
+    status: Option<reqwest::StatusCode>,
+    body: Stream<bytes::Bytes>,
+    decoder: PageDecoder<E>,
+    bytes: usize,
+    entries: usize,
+    done: bool,
+}
+
+impl<E: DeserializeOwned> PageBody<E> {
+    /// Issue the request for the page if that did not happen yet, and decode
+    /// the next entry, receiving more of the body as needed.
+    async fn next(&mut self, span: &RequestSpan) -> Result<PageChunk<E>> {
+        if let Some(request) = self.request.take() {
+            let response = self.client.execute(request?, span).await?;
+            self.status = Some(response.status);
+            self.decoder = PageDecoder::new(&response.url);
+            self.body = response.into_body();
+        }
+        loop {
+            match self.decoder.next()? {
+                Step::Entry(entry) => {
+                    self.entries += 1;
+                    return Ok(PageChunk::Entry(entry));
                 }
-                Err(e) => future::err(error::Error::from(e)).boxed(),
-            })
+                Step::Done(cursor) => return Ok(PageChunk::End(cursor)),
+                Step::NeedMore => (),
+            }
+            match self.body.next().await {
+                Some(chunk) => {
+                    let chunk = chunk?;
+                    self.bytes += chunk.len();
+                    self.decoder.push(&chunk);
+                }
+                None => self.decoder.finish(),
+            }
+        }
+    }
+}
+
+/// Drive the given stream in a background task, buffering up to `depth`
+/// entries ahead of the consumer. Errors are delivered in order, once the
+/// consumer reaches them. The task is started when the returned stream is
+/// first polled, and stopped when it is dropped.
+fn prefetch<T: Send + 'static>(mut entries: Stream<T>, depth: usize) -> Stream<T> {
+    struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+    impl Drop for AbortOnDrop {
+        fn drop(&mut self) {
+            self.0.abort();
+        }
+    }
+
+    stream::once(async move {
+        let (sender, receiver) = tokio::sync::mpsc::channel(depth);
+        let task = tokio::spawn(async move {
+            // Reserve room for an entry before fetching it, so at most
+            // `depth` entries are held ahead of the consumer.
+            while let Ok(permit) = sender.reserve().await {
+                let entry = match entries.next().await {
+                    Some(entry) => entry,
+                    None => break,
+                };
+                let failed = entry.is_err();
+                permit.send(entry);
+                if failed {
+                    break;
+                }
+            }
+        });
+        stream::unfold(
+            (receiver, AbortOnDrop(task)),
+            |(mut receiver, task)| async move {
+                let entry = receiver.recv().await?;
+                Some((entry, (receiver, task)))
+            },
+        )
+    })
+    .flatten()
+    .boxed()
+}
+
+/// Decode the JSON body of the given response.
+async fn decode<T: DeserializeOwned>(response: transport::Response) -> Result<T> {
+    let url = response.url.clone();
+    let body = response.bytes().await?;
+    serde_json::from_slice(&body).map_err(|err| Error::decode(&url, &body, err))
+}
+
+impl<S: ?Sized, T> IntoItems<T> for S where S: StdStream<Item = Result<Page<T>>> {}
+
+/// Flattens a stream of pages into a stream of their entries.
+pub trait IntoItems<T>: StdStream<Item = Result<Page<T>>> {
+    fn into_items(self) -> Stream<T>
+    where
+        Self: Sized + Send + 'static,
+        T: Send + 'static,
+    {
+        self.map_ok(|page| stream::iter(page.items.into_iter().map(Ok)))
+            .try_flatten()
             .boxed()
     }
 }
@@ -184,15 +746,328 @@ pub trait IntoVec: StreamExt {
     }
 }
 
+/// Returns a client for the test running on the current thread.
+///
+/// By default the client replays the fixture of the test from the `fixtures`
+/// directory. With `HELIUM_API_FIXTURES=record` the test is run against the
+/// live API and its fixture is recorded again.
 #[cfg(test)]
 fn get_test_client() -> Client {
-    use std::{env, thread, time};
+    use std::{env, path::Path, thread, time};
     const USER_AGENT: &str = "helium-api-test/0.1.0";
     const BASE_URL: &str = "https://api.helium.io/v1";
-    let duration = time::Duration::from_millis(env::var("TEST_DELAY_MS").map_or(0, |v| {
-        v.parse::<u64>()
-            .expect("TEST_DELAY_MS cannot be parsed as u64")
-    }));
-    thread::sleep(duration);
-    Client::new_with_base_url(BASE_URL.into(), USER_AGENT)
+    let test_name = thread::current()
+        .name()
+        .expect("test thread name")
+        .replace("::test::", "::")
+        .replace("::", "/");
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(test_name)
+        .with_extension("json");
+    let builder = ClientBuilder::new(BASE_URL).user_agent(USER_AGENT);
+    let builder = if matches!(env::var("HELIUM_API_FIXTURES").as_deref(), Ok("record")) {
+        let duration = time::Duration::from_millis(env::var("TEST_DELAY_MS").map_or(0, |v| {
+            v.parse::<u64>()
+                .expect("TEST_DELAY_MS cannot be parsed as u64")
+        }));
+        thread::sleep(duration);
+        let transport = builder.clone().build().expect("live client").transport;
+        builder.transport(fixture::RecordingTransport::new(transport, path))
+    } else {
+        let fixture = fixture::Fixture::load(&path)
+            .unwrap_or_else(|err| panic!("fixture {}: {:?}", path.display(), err));
+        builder.transport(fixture.replay().expect("replay fixture"))
+    };
+    builder.build().expect("test client")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use tokio::test;
+    use transport::MemoryTransport;
+
+    fn memory_client(transport: &MemoryTransport) -> Client {
+        Client::builder()
+            .base_url("http://localhost/v1")
+            .transport(transport.clone())
+            .retry_policy(RetryPolicy::default().initial_backoff(Duration::from_millis(1)))
+            .build()
+            .expect("client")
+    }
+
+    #[test]
+    async fn fetch_stream_pages() {
+        let transport = MemoryTransport::new()
+            .with_json("/ouis", json!({"data": [1, 2], "cursor": "a"}))
+            .with_json_query(
+                "/ouis",
+                &[("cursor", "a")],
+                json!({"data": [], "cursor": "b"}),
+            )
+            .with_json_query("/ouis", &[("cursor", "b")], json!({"data": [3]}));
+        let client = memory_client(&transport);
+        let entries: Vec<u64> = client
+            .fetch_stream("/ouis", NO_QUERY)
+            .into_vec()
+            .await
+            .expect("entries");
+        assert_eq!(entries, vec![1, 2, 3]);
+
+        let pages: Vec<Page<u64>> = client
+            .fetch_pages("/ouis", NO_QUERY, Some("a"))
+            .into_vec()
+            .await
+            .expect("pages");
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].cursor.as_deref(), Some("b"));
+        assert_eq!(pages[1].items, vec![3]);
+        assert_eq!(pages[1].cursor, None);
+    }
+
+    #[test]
+    async fn retry() {
+        let transport = MemoryTransport::new()
+            .with_status("/blocks/height", reqwest::StatusCode::SERVICE_UNAVAILABLE)
+            .with_json("/blocks/height", json!({"data": {"height": 10}}));
+        let client = memory_client(&transport);
+        assert_eq!(blocks::height(&client).await.expect("height"), 10);
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[test]
+    async fn retry_after() {
+        for status in [
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            reqwest::StatusCode::SERVICE_UNAVAILABLE,
+        ] {
+            let transport = MemoryTransport::new();
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert(
+                reqwest::header::RETRY_AFTER,
+                reqwest::header::HeaderValue::from_static("0"),
+            );
+            transport
+                .insert(
+                    reqwest::Method::GET,
+                    "/blocks/height",
+                    None::<&()>,
+                    status,
+                    headers,
+                    bytes::Bytes::new(),
+                )
+                .expect("route");
+            let transport = transport.with_json("/blocks/height", json!({"data": {"height": 10}}));
+            // The backoff would outlast the timeout if Retry-After was ignored
+            let client = Client::builder()
+                .transport(transport.clone())
+                .retry_policy(RetryPolicy::default().initial_backoff(Duration::from_secs(60)))
+                .build()
+                .expect("client");
+            let height = tokio::time::timeout(Duration::from_secs(5), blocks::height(&client))
+                .await
+                .expect("retry after the Retry-After delay")
+                .expect("height");
+            assert_eq!(height, 10, "{}", status);
+            assert_eq!(transport.requests().len(), 2);
+        }
+    }
+
+    #[test]
+    async fn retry_after_capped() {
+        let transport = MemoryTransport::new();
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::RETRY_AFTER,
+            reqwest::header::HeaderValue::from_static("3600"),
+        );
+        transport
+            .insert(
+                reqwest::Method::GET,
+                "/blocks/height",
+                None::<&()>,
+                reqwest::StatusCode::SERVICE_UNAVAILABLE,
+                headers,
+                bytes::Bytes::new(),
+            )
+            .expect("route");
+        let transport = transport.with_json("/blocks/height", json!({"data": {"height": 10}}));
+        // An hour long Retry-After is only honored up to the maximum backoff
+        let client = Client::builder()
+            .transport(transport.clone())
+            .retry_policy(RetryPolicy::default().max_backoff(Duration::from_millis(10)))
+            .build()
+            .expect("client");
+        let height = tokio::time::timeout(Duration::from_secs(5), blocks::height(&client))
+            .await
+            .expect("retry after the maximum backoff")
+            .expect("height");
+        assert_eq!(height, 10);
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[test]
+    async fn cache() {
+        let transport = MemoryTransport::new()
+            .with_json(
+                "/oracle/prices/10",
+                json!({"data": {"price": 1, "block": 10}}),
+            )
+            .with_json(
+                "/oracle/prices/current",
+                json!({"data": {"price": 1, "block": 10}}),
+            );
+        let client = Client::builder()
+            .transport(transport.clone())
+            .cache(Cache::new(10))
+            .build()
+            .expect("client");
+        for _ in 0..3 {
+            oracle::prices::at_block(&client, 10).await.expect("price");
+            oracle::prices::current(&client).await.expect("price");
+        }
+        assert_eq!(transport.requests().len(), 4);
+        let stats = client.cache().expect("cache").stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (2, 1, 1));
+    }
+
+    #[test]
+    async fn prefetch() {
+        let transport = MemoryTransport::new()
+            .with_json("/ouis", json!({"data": [1], "cursor": "a"}))
+            .with_json_query(
+                "/ouis",
+                &[("cursor", "a")],
+                json!({"data": [2], "cursor": "b"}),
+            )
+            .with_json_query(
+                "/ouis",
+                &[("cursor", "b")],
+                json!({"data": [3], "cursor": "c"}),
+            )
+            .with_json_query("/ouis", &[("cursor", "c")], json!("invalid"));
+        let client = Client::builder()
+            .transport(transport.clone())
+            .prefetch(1)
+            .build()
+            .expect("client");
+        let mut pages = client.fetch_pages::<u64, _>("/ouis", NO_QUERY, None);
+        let first = pages.next().await.expect("page").expect("page");
+        assert_eq!(first.items, [1]);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        // One page is buffered and the next one is only fetched once there is
+        // room for it in the buffer
+        assert_eq!(transport.requests().len(), 2);
+        let rest: Vec<Result<Page<u64>>> = pages.collect().await;
+        assert_eq!(rest[0].as_ref().expect("page").items, [2]);
+        assert_eq!(rest[1].as_ref().expect("page").items, [3]);
+        assert!(matches!(rest[2], Err(Error::Decode { .. })));
+        assert_eq!(rest.len(), 3);
+    }
+
+    #[test]
+    async fn get_many() {
+        let transport = MemoryTransport::new()
+            .with_json("/hotspots/a", json!({"data": {"address": "a"}}))
+            .with_json("/hotspots/c", json!({"data": {"address": "c"}}));
+        let client = memory_client(&transport);
+        let results: Vec<(String, Result<serde_json::Value>)> = client
+            .get_many("/hotspots", ["a", "b", "c"], 2, Order::Input)
+            .collect()
+            .await;
+        let addresses: Vec<_> = results
+            .iter()
+            .map(|(address, _)| address.as_str())
+            .collect();
+        assert_eq!(addresses, ["a", "b", "c"]);
+        assert_eq!(results[0].1.as_ref().expect("a")["address"], "a");
+        assert!(results[1].1.as_ref().unwrap_err().is_not_found());
+        assert_eq!(results[2].1.as_ref().expect("c")["address"], "c");
+
+        let mut completed: Vec<(String, bool)> = client
+            .get_many::<serde_json::Value, _, _>("/hotspots", ["a", "b", "c"], 3, Order::Completion)
+            .map(|(address, result)| (address, result.is_ok()))
+            .collect()
+            .await;
+        completed.sort();
+        assert_eq!(
+            completed,
+            [("a".into(), true), ("b".into(), false), ("c".into(), true)]
+        );
+
+        // Lookups start as the stream is consumed, one at a time for a
+        // concurrency of zero
+        let requests = transport.requests().len();
+        let mut batch = client.get_many::<serde_json::Value, _, _>(
+            "/hotspots",
+            vec!["a".to_string(), "c".to_string()],
+            0,
+            Order::Input,
+        );
+        assert_eq!(transport.requests().len(), requests);
+        assert_eq!(batch.next().await.expect("a").0, "a");
+        assert_eq!(transport.requests().len(), requests + 1);
+    }
+
+    #[test]
+    async fn raw_json() {
+        #[derive(Debug, Deserialize)]
+        struct Named {
+            name: String,
+        }
+
+        let transport = MemoryTransport::new()
+            .with_json(
+                "/hotspots/a",
+                json!({"data": {"name": "a", "mode": "full"}}),
+            )
+            .with_json("/hotspots", json!({"data": [{"name": "a"}, {"name": "b"}]}));
+        let client = memory_client(&transport);
+        let json = client
+            .get_json("/hotspots/a", NO_QUERY)
+            .await
+            .expect("json");
+        assert_eq!(json["mode"], "full");
+        let hotspot: WithRaw<Named> = client.get("/hotspots/a", NO_QUERY).await.expect("hotspot");
+        assert_eq!(hotspot.name, "a");
+        assert_eq!(hotspot.raw["mode"], "full");
+        let names: Vec<_> = client
+            .stream_json("/hotspots", NO_QUERY)
+            .map_ok(|hotspot| hotspot["name"].clone())
+            .into_vec()
+            .await
+            .expect("hotspots");
+        assert_eq!(names, ["a", "b"]);
+        let err = client
+            .get::<WithRaw<Named>, _>("/hotspots", NO_QUERY)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Decode { .. }));
+    }
+
+    #[test]
+    async fn not_found() {
+        let transport = MemoryTransport::new();
+        let client = memory_client(&transport);
+        let err = hotspots::get(&client, "unknown").await.unwrap_err();
+        assert!(err.is_not_found());
+    }
+
+    #[test]
+    async fn post() {
+        let transport = MemoryTransport::new()
+            .with_post_json("/pending_transactions", json!({"data": {"hash": "abc"}}));
+        let client = memory_client(&transport);
+        let status = pending_transactions::submit(&client, b"txn")
+            .await
+            .expect("status");
+        assert_eq!(status.hash, "abc");
+        let requests = transport.requests();
+        assert_eq!(
+            requests[0].body.as_deref(),
+            Some(br#"{"txn":"dHhu"}"#.as_ref())
+        );
+    }
 }
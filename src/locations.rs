@@ -0,0 +1,23 @@
+use crate::{models::Geocode, *};
+
+/// Get the geocode of the given h3 hex location
+pub async fn get(client: &Client, location: &str) -> Result<Geocode> {
+    client
+        .fetch(&format!("/locations/{}", location), NO_QUERY)
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio::test;
+
+    #[test]
+    async fn get() {
+        let client = get_test_client();
+        let geocode = locations::get(&client, "8838a162e3fffff")
+            .await
+            .expect("geocode");
+        assert_eq!(geocode.short_country.as_deref(), Some("PT"));
+    }
+}
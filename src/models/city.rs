@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// A city with hotspots, as found by the geocode of their locations.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct City {
+    /// The id of the city
+    pub city_id: String,
+    /// The long version of the name of the city
+    pub long_city: Option<String>,
+    /// The long version of the state of the city
+    pub long_state: Option<String>,
+    /// The long version of the country of the city
+    pub long_country: Option<String>,
+    /// The short version of the name of the city
+    pub short_city: Option<String>,
+    /// The short version of the state of the city
+    pub short_state: Option<String>,
+    /// The short version of the country of the city
+    pub short_country: Option<String>,
+    /// The number of hotspots in the city
+    pub hotspot_count: u64,
+    /// The number of online hotspots in the city
+    pub online_count: u64,
+    /// The number of offline hotspots in the city
+    pub offline_count: u64,
+}
@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Geocode {
+    /// The id of the city of the location, to look up the city in the
+    /// `cities` module
+    pub city_id: Option<String>,
     /// The long version of city for the last asserted location
     pub long_city: Option<String>,
     /// The long version of country for the last asserted location
@@ -3,7 +3,7 @@ use crate::{Error, Result};
 use chrono::{DateTime, Utc};
 
 use serde::{de, Deserialize, Serialize};
-use std::{fmt, str::FromStr};
+use std::{collections::BTreeMap, fmt, str::FromStr};
 
 use super::Geocode;
 
@@ -45,6 +45,10 @@ pub struct Hotspot {
     pub reward_scale: Option<f64>,
     /// The current status for the hotspot
     pub status: Status,
+    /// The distance (in meters) from the requested location. This field is
+    /// only set when searching for hotspots near a location
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance: Option<f64>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -116,3 +120,43 @@ impl FromStr for HotspotStakingMode {
         }
     }
 }
+
+/// A hotspot in the witness list of another hotspot, with the witness
+/// reports the other hotspot has of it.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Witness {
+    #[serde(flatten)]
+    pub hotspot: Hotspot,
+    /// The address of the hotspot this hotspot is a witness for
+    pub witness_for: Option<String>,
+    /// The witness reports of this hotspot, if known
+    pub witness_info: Option<WitnessInfo>,
+}
+
+impl std::ops::Deref for Witness {
+    type Target = Hotspot;
+
+    fn deref(&self) -> &Hotspot {
+        &self.hotspot
+    }
+}
+
+/// The witness reports of a hotspot for another hotspot.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WitnessInfo {
+    /// The number of witness reports by their RSSI in dBm
+    pub histogram: BTreeMap<i32, u64>,
+    /// The time of the first witness report
+    #[serde(with = "chrono::serde::ts_nanoseconds")]
+    pub first_time: DateTime<Utc>,
+    /// The time of the most recent witness report
+    #[serde(with = "chrono::serde::ts_nanoseconds")]
+    pub recent_time: DateTime<Utc>,
+}
+
+impl WitnessInfo {
+    /// The total number of witness reports
+    pub fn count(&self) -> u64 {
+        self.histogram.values().sum()
+    }
+}
@@ -1,24 +1,30 @@
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 
 mod account;
 mod block;
+mod city;
 mod geocode;
 mod hotspot;
 mod oracle;
 mod oui;
+mod reward;
 pub mod transactions;
 mod validator;
 mod values;
 
 pub use account::*;
 pub use block::*;
+pub use city::*;
 pub use geocode::*;
 pub use hotspot::*;
 pub use oracle::*;
 pub use oui::*;
+pub use reward::*;
 pub use validator::*;
 pub use values::*;
 
+use transactions::TransactionType;
+
 /// Query params for requests that can take in a time range.
 #[derive(Clone, Debug, Serialize)]
 pub struct QueryTimeRange {
@@ -27,3 +33,39 @@ pub struct QueryTimeRange {
     /// ISO 8601 timestamp or relative time (-3 hour) maximum time range
     pub max_time: String,
 }
+
+/// Query params for activity requests, which can take in a time range and
+/// the types of transactions to return.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct QueryActivity {
+    /// ISO 8601 timestamp or relative time (-3 hour) minimum time range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_time: Option<String>,
+    /// ISO 8601 timestamp or relative time (-3 hour) maximum time range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_time: Option<String>,
+    /// Only return transactions of these types, or all transactions if empty
+    #[serde(
+        serialize_with = "serialize_filter_types",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub filter_types: Vec<TransactionType>,
+}
+
+impl From<QueryTimeRange> for QueryActivity {
+    fn from(range: QueryTimeRange) -> Self {
+        Self {
+            min_time: Some(range.min_time),
+            max_time: Some(range.max_time),
+            filter_types: vec![],
+        }
+    }
+}
+
+fn serialize_filter_types<S: Serializer>(
+    types: &[TransactionType],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let types: Vec<&str> = types.iter().map(TransactionType::as_str).collect();
+    serializer.serialize_str(&types.join(","))
+}
@@ -0,0 +1,114 @@
+use super::{Hnt, QueryTimeRange};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A reward earned by a hotspot or validator
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Reward {
+    /// The owner address is the base58 check-encoded public key of
+    /// the owner's wallet address.
+    pub account: String,
+    /// The reward amount.
+    #[serde(deserialize_with = "Hnt::deserialize")]
+    pub amount: Hnt,
+    /// The block the reward was earned in.
+    pub block: i64,
+    /// The hotspot or validator address is the base58 check-encoded public
+    /// key of the hotspot or validator.
+    pub gateway: String,
+    /// The transaction hash of the reward.
+    pub hash: String,
+    /// The timestamp of the rewards.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// The sum of the rewards earned in a time range, with statistics of the
+/// individual rewards.
+///
+/// The API also returns the sum as a float number of HNT in `total`. It is
+/// dropped since `sum` holds the same amount exactly.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RewardSum {
+    /// The sum of the rewards
+    pub sum: Hnt,
+    /// The smallest reward
+    #[serde(with = "hnt_float")]
+    pub min: Hnt,
+    /// The largest reward
+    #[serde(with = "hnt_float")]
+    pub max: Hnt,
+    /// The median reward
+    #[serde(with = "hnt_float")]
+    pub median: Hnt,
+    /// The average reward, rounded to the nearest bone
+    #[serde(with = "hnt_float")]
+    pub avg: Hnt,
+    /// The standard deviation of the rewards, rounded to the nearest bone
+    #[serde(with = "hnt_float")]
+    pub stddev: Hnt,
+}
+
+/// The reward statistics are given as float numbers of HNT rather than
+/// bones.
+mod hnt_float {
+    use super::Hnt;
+    use rust_decimal::prelude::*;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(hnt: &Hnt, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_f64(hnt.to_f64())
+    }
+
+    pub fn deserialize<'de, D>(d: D) -> Result<Hnt, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = f64::deserialize(d)?;
+        match Decimal::from_f64(value) {
+            Some(decimal) => Ok(Hnt::new(decimal.round_dp(8))),
+            None => Err(D::Error::custom(format!("invalid HNT amount {}", value))),
+        }
+    }
+}
+
+/// The sum of the rewards earned in one bucket of a time range.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RewardBucket {
+    /// The start of the bucket
+    pub timestamp: DateTime<Utc>,
+    /// The sum of the rewards earned in the bucket
+    #[serde(flatten)]
+    pub rewards: RewardSum,
+}
+
+/// The size of the buckets a time range is split into for reward sums.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Bucket {
+    Hour,
+    Day,
+    Week,
+}
+
+/// Reward sums for a time range, as returned by `rewards_sum`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum RewardSums {
+    /// The sum of the rewards in the whole time range
+    Total(RewardSum),
+    /// The sums of the rewards in each bucket of the time range, newest
+    /// first, when a bucket size was requested
+    Buckets(Vec<RewardBucket>),
+}
+
+/// Query params for reward sums, optionally split into buckets.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct QueryRewardSum<'a> {
+    #[serde(flatten)]
+    pub(crate) range: &'a QueryTimeRange,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) bucket: Option<Bucket>,
+}
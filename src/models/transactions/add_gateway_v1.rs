@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -10,3 +11,15 @@ pub struct AddGatewayV1 {
     pub gateway: String,
     pub staking_fee: Hnt,
 }
+
+transaction_info! {
+    AddGatewayV1, "add_gateway_v1", [fee, payer];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.owner.as_str(), AddressRole::Owner),
+            (&self.payer, AddressRole::Payer),
+            (&self.gateway, AddressRole::Gateway),
+        ]
+    }
+}
@@ -0,0 +1,18 @@
+use super::{subnetwork_amounts, transaction_info, SubnetworkAmount, SubnetworkTokenType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(remote = "Self")]
+pub struct AddSubnetworkV1 {
+    pub hash: String,
+    pub token_type: SubnetworkTokenType,
+    pub subnetwork_key: String,
+    #[serde(default)]
+    pub reward_server_keys: Vec<String>,
+    /// The amount of tokens minted to the subnetwork when it was added
+    pub premine: SubnetworkAmount,
+}
+
+subnetwork_amounts!(AddSubnetworkV1, ["premine"]);
+
+transaction_info!(AddSubnetworkV1, "add_subnetwork_v1", []);
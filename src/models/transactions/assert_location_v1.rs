@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -12,3 +13,22 @@ pub struct AssertLocationV1 {
     pub location: String,
     pub staking_fee: Hnt,
 }
+
+transaction_info! {
+    AssertLocationV1, "assert_location_v1", [fee, nonce];
+
+    fn payer(&self) -> Option<&str> {
+        self.payer.as_deref()
+    }
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        let mut addresses = vec![
+            (self.owner.as_str(), AddressRole::Owner),
+            (&self.gateway, AddressRole::Gateway),
+        ];
+        if let Some(payer) = &self.payer {
+            addresses.push((payer.as_str(), AddressRole::Payer));
+        }
+        addresses
+    }
+}
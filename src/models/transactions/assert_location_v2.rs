@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -14,3 +15,22 @@ pub struct AssertLocationV2 {
     pub elevation: i64,
     pub staking_fee: Hnt,
 }
+
+transaction_info! {
+    AssertLocationV2, "assert_location_v2", [fee, nonce];
+
+    fn payer(&self) -> Option<&str> {
+        self.payer.as_deref()
+    }
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        let mut addresses = vec![
+            (self.owner.as_str(), AddressRole::Owner),
+            (&self.gateway, AddressRole::Gateway),
+        ];
+        if let Some(payer) = &self.payer {
+            addresses.push((payer.as_str(), AddressRole::Payer));
+        }
+        addresses
+    }
+}
@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -10,3 +11,15 @@ pub struct ConsensusGroupFailureV1 {
     pub failed_members: Vec<String>,
     pub signatures: Vec<String>,
 }
+
+transaction_info! {
+    ConsensusGroupFailureV1, "consensus_group_failure_v1", [];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        self.members
+            .iter()
+            .chain(&self.failed_members)
+            .map(|member| (member.as_str(), AddressRole::ConsensusMember))
+            .collect()
+    }
+}
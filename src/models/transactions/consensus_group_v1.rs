@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -8,3 +9,14 @@ pub struct ConsensusGroupV1 {
     pub members: Vec<String>,
     pub proof: String,
 }
+
+transaction_info! {
+    ConsensusGroupV1, "consensus_group_v1", [];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        self.members
+            .iter()
+            .map(|member| (member.as_str(), AddressRole::ConsensusMember))
+            .collect()
+    }
+}
@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -13,3 +14,14 @@ pub struct CreateHtlcV1 {
     pub hashlock: String,
     pub timelock: u64,
 }
+
+transaction_info! {
+    CreateHtlcV1, "create_htlc_v1", [fee, nonce, payer];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.payer.as_str(), AddressRole::Payer),
+            (&self.payee, AddressRole::Payee),
+        ]
+    }
+}
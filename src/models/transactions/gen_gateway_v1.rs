@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -8,3 +9,14 @@ pub struct GenGatewayV1 {
     pub gateway: String,
     pub location: String,
 }
+
+transaction_info! {
+    GenGatewayV1, "gen_gateway_v1", [nonce];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.owner.as_str(), AddressRole::Owner),
+            (&self.gateway, AddressRole::Gateway),
+        ]
+    }
+}
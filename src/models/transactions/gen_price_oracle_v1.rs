@@ -1,3 +1,4 @@
+use super::transaction_info;
 use crate::models::Usd;
 use serde::{Deserialize, Serialize};
 
@@ -7,3 +8,5 @@ pub struct GenPriceOracleV1 {
     #[serde(deserialize_with = "Usd::deserialize")]
     pub price: Usd,
 }
+
+transaction_info!(GenPriceOracleV1, "gen_price_oracle_v1", []);
@@ -0,0 +1,22 @@
+use super::{transaction_info, AddressRole};
+use crate::models::Hnt;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct GenValidatorV1 {
+    pub hash: String,
+    pub address: String,
+    pub owner: String,
+    pub stake: Hnt,
+}
+
+transaction_info! {
+    GenValidatorV1, "gen_validator_v1", [];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.owner.as_str(), AddressRole::Owner),
+            (&self.address, AddressRole::Validator),
+        ]
+    }
+}
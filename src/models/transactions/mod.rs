@@ -1,4 +1,5 @@
 mod add_gateway_v1;
+mod add_subnetwork_v1;
 mod assert_location_v1;
 mod assert_location_v2;
 mod coinbase_v1;
@@ -8,6 +9,7 @@ mod create_htlc_v1;
 mod dc_coinbase_v1;
 mod gen_gateway_v1;
 mod gen_price_oracle_v1;
+mod gen_validator_v1;
 mod oui_v1;
 mod payment_v1;
 mod payment_v2;
@@ -26,16 +28,23 @@ mod security_exchange_v1;
 mod stake_validator_v1;
 mod state_channel_close_v1;
 mod state_channel_open_v1;
+mod subnetwork_fund_v1;
+mod subnetwork_rewards_v1;
+mod subnetwork_token;
 mod token_burn_v1;
 mod token_exchange_rate_v1;
+mod token_redeem_v1;
 mod transfer_hotspot_v1;
+mod transfer_hotspot_v2;
 mod transfer_validator_stake_v1;
 mod unstake_validator_v1;
 mod update_gateway_oui_v1;
+mod update_subnetwork_v1;
 mod validator_heartbeat_v1;
 mod vars_v1;
 
 pub use add_gateway_v1::*;
+pub use add_subnetwork_v1::*;
 pub use assert_location_v1::*;
 pub use assert_location_v2::*;
 pub use coinbase_v1::*;
@@ -45,10 +54,14 @@ pub use create_htlc_v1::*;
 pub use dc_coinbase_v1::*;
 pub use gen_gateway_v1::*;
 pub use gen_price_oracle_v1::*;
+pub use gen_validator_v1::*;
 pub use oui_v1::*;
 pub use payment_v1::*;
 pub use payment_v2::*;
 pub use pending_txn_status::*;
+// Both receipt versions define their own `PathElement`, `Receipt` and
+// `Witness`, which stay ambiguous as before.
+#[allow(ambiguous_glob_reexports)]
 pub use poc_receipts_v1::*;
 pub use poc_receipts_v2::*;
 pub use poc_request_v1::*;
@@ -63,62 +76,456 @@ pub use security_exchange_v1::*;
 pub use stake_validator_v1::*;
 pub use state_channel_close_v1::*;
 pub use state_channel_open_v1::*;
+pub use subnetwork_fund_v1::*;
+pub use subnetwork_rewards_v1::*;
+pub use subnetwork_token::*;
 pub use token_burn_v1::*;
 pub use token_exchange_rate_v1::*;
+pub use token_redeem_v1::*;
 pub use transfer_hotspot_v1::*;
+pub use transfer_hotspot_v2::*;
 pub use transfer_validator_stake_v1::*;
 pub use unstake_validator_v1::*;
 pub use update_gateway_oui_v1::*;
+pub use update_subnetwork_v1::*;
 pub use validator_heartbeat_v1::*;
 pub use vars_v1::*;
 
-use serde::{Deserialize, Serialize};
-
-#[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(tag = "type", rename_all = "snake_case")]
-/// Represents one of various transactions in a block on the chain.
-pub enum Transaction {
-    AddGatewayV1(AddGatewayV1),
-    AssertLocationV1(AssertLocationV1),
-    AssertLocationV2(AssertLocationV2),
-    CoinbaseV1(CoinbaseV1),
-    ConsensusGroupFailureV1(ConsensusGroupFailureV1),
-    ConsensusGroupV1(ConsensusGroupV1),
-    CreateHtlcV1(CreateHtlcV1),
-    DcCoinbaseV1(DcCoinbaseV1),
-    GenGatewayV1(GenGatewayV1),
-    GenPriceOracleV1(GenPriceOracleV1),
-    OuiV1(OuiV1),
-    PaymentV1(PaymentV1),
-    PaymentV2(PaymentV2),
-    PocReceiptsV1(PocReceiptsV1),
-    PocReceiptsV2(PocReceiptsV2),
-    PocRequestV1(PocRequestV1),
-    PriceOracleV1(PriceOracleV1),
-    RedeemHtlcV1(RedeemHtlcV1),
-    RewardsV1(RewardsV1),
-    RewardsV2(RewardsV2),
-    RoutingV1(RoutingV1),
-    SecurityCoinbaseV1(SecurityCoinbaseV1),
-    SecurityExchangeV1(SecurityExchangeV1),
-    StakeValidatorV1(StakeValidatorV1),
-    StateChannelCloseV1(StateChannelCloseV1),
-    StateChannelOpenV1(StateChannelOpenV1),
-    TokenBurnExchangeRateV1(TokenBurnExchangeRateV1),
-    TokenBurnV1(TokenBurnV1),
-    TransferHotspotV1(TransferHotspotV1),
-    TransferValidatorStakeV1(TransferValidatorStakeV1),
-    UnstakeValidatorV1(UnstakeValidatorV1),
-    UpdateGatewayOuiV1(UpdateGatewayOuiV1),
-    ValidatorHeartbeatV1(ValidatorHeartbeatV1),
-    VarsV1(VarsV1),
-    #[serde(other)]
-    Unknown, // Any other transaction not supported
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::fmt;
+
+/// Defines the `Transaction` and `TransactionType` enums from the type names
+/// and models of all supported transactions.
+/// Implement `TransactionInfo` for a transaction type with the given type
+/// name. The listed `fee`, `nonce` and `payer` are read from the fields of the
+/// same name, or from the field given after `=`. Any other methods follow
+/// the list.
+macro_rules! transaction_info {
+    (
+        $txn:ident, $type_name:literal, [$($method:ident $(= $field:ident)?),* $(,)?]
+        $(; $($rest:tt)*)?
+    ) => {
+        impl $crate::models::transactions::TransactionInfo for $txn {
+            fn hash(&self) -> &str {
+                &self.hash
+            }
+
+            fn type_name(&self) -> &str {
+                $type_name
+            }
+
+            $(transaction_info!(@$method $($field)? $method);)*
+
+            $($($rest)*)?
+        }
+    };
+    (@fee $field:ident $($_default:ident)?) => {
+        fn fee(&self) -> Option<u64> {
+            Some(self.$field)
+        }
+    };
+    (@nonce $field:ident $($_default:ident)?) => {
+        fn nonce(&self) -> Option<u64> {
+            Some(self.$field)
+        }
+    };
+    (@payer $field:ident $($_default:ident)?) => {
+        fn payer(&self) -> Option<&str> {
+            Some(&self.$field)
+        }
+    };
+}
+pub(crate) use transaction_info;
+
+macro_rules! transactions {
+    ($($type_name:literal => $variant:ident,)*) => {
+        #[derive(Clone, Serialize, Deserialize, Debug)]
+        #[serde(remote = "Self", tag = "type")]
+        /// Represents one of various transactions in a block on the chain.
+        pub enum Transaction {
+            $(
+                #[serde(rename = $type_name)]
+                $variant($variant),
+            )*
+            /// Any other transaction not supported
+            #[serde(skip)]
+            Unknown(UnknownTransaction),
+        }
+
+        impl TransactionInfo for Transaction {
+            fn hash(&self) -> &str {
+                match self {
+                    $(Self::$variant(txn) => txn.hash(),)*
+                    Self::Unknown(txn) => &txn.hash,
+                }
+            }
+
+            fn type_name(&self) -> &str {
+                match self {
+                    $(Self::$variant(txn) => txn.type_name(),)*
+                    Self::Unknown(txn) => &txn.type_name,
+                }
+            }
+
+            fn fee(&self) -> Option<u64> {
+                match self {
+                    $(Self::$variant(txn) => txn.fee(),)*
+                    Self::Unknown(txn) => txn.raw.get("fee").and_then(Value::as_u64),
+                }
+            }
+
+            fn nonce(&self) -> Option<u64> {
+                match self {
+                    $(Self::$variant(txn) => txn.nonce(),)*
+                    Self::Unknown(txn) => txn.raw.get("nonce").and_then(Value::as_u64),
+                }
+            }
+
+            fn payer(&self) -> Option<&str> {
+                match self {
+                    $(Self::$variant(txn) => txn.payer(),)*
+                    Self::Unknown(txn) => txn.raw.get("payer").and_then(Value::as_str),
+                }
+            }
+
+            fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+                match self {
+                    $(Self::$variant(txn) => txn.involved_addresses(),)*
+                    Self::Unknown(txn) => txn
+                        .raw
+                        .get("payer")
+                        .and_then(Value::as_str)
+                        .map(|payer| (payer, AddressRole::Payer))
+                        .into_iter()
+                        .collect(),
+                }
+            }
+        }
+
+        impl Transaction {
+            /// Whether the given transaction type is one of the supported
+            /// transactions.
+            fn is_supported(type_name: &str) -> bool {
+                matches!(type_name, $($type_name)|*)
+            }
+
+            /// The type of the transaction, or `None` for an unsupported
+            /// transaction.
+            pub fn transaction_type(&self) -> Option<TransactionType> {
+                match self {
+                    $(Self::$variant(_) => Some(TransactionType::$variant),)*
+                    Self::Unknown(_) => None,
+                }
+            }
+        }
+
+        /// The type of a supported transaction, for example to only request
+        /// activity of the given types.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        pub enum TransactionType {
+            $(
+                #[serde(rename = $type_name)]
+                $variant,
+            )*
+        }
+
+        impl TransactionType {
+            /// The type name as used by the API, like `payment_v2`
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $type_name,)*
+                }
+            }
+        }
+    };
+}
+
+transactions! {
+    "add_gateway_v1" => AddGatewayV1,
+    "add_subnetwork_v1" => AddSubnetworkV1,
+    "assert_location_v1" => AssertLocationV1,
+    "assert_location_v2" => AssertLocationV2,
+    "coinbase_v1" => CoinbaseV1,
+    "consensus_group_failure_v1" => ConsensusGroupFailureV1,
+    "consensus_group_v1" => ConsensusGroupV1,
+    "create_htlc_v1" => CreateHtlcV1,
+    "dc_coinbase_v1" => DcCoinbaseV1,
+    "gen_gateway_v1" => GenGatewayV1,
+    "gen_price_oracle_v1" => GenPriceOracleV1,
+    "gen_validator_v1" => GenValidatorV1,
+    "oui_v1" => OuiV1,
+    "payment_v1" => PaymentV1,
+    "payment_v2" => PaymentV2,
+    "poc_receipts_v1" => PocReceiptsV1,
+    "poc_receipts_v2" => PocReceiptsV2,
+    "poc_request_v1" => PocRequestV1,
+    "price_oracle_v1" => PriceOracleV1,
+    "redeem_htlc_v1" => RedeemHtlcV1,
+    "rewards_v1" => RewardsV1,
+    "rewards_v2" => RewardsV2,
+    "routing_v1" => RoutingV1,
+    "security_coinbase_v1" => SecurityCoinbaseV1,
+    "security_exchange_v1" => SecurityExchangeV1,
+    "stake_validator_v1" => StakeValidatorV1,
+    "state_channel_close_v1" => StateChannelCloseV1,
+    "state_channel_open_v1" => StateChannelOpenV1,
+    "subnetwork_fund_v1" => SubnetworkFundV1,
+    "subnetwork_rewards_v1" => SubnetworkRewardsV1,
+    "token_burn_exchange_rate_v1" => TokenBurnExchangeRateV1,
+    "token_burn_v1" => TokenBurnV1,
+    "token_redeem_v1" => TokenRedeemV1,
+    "transfer_hotspot_v1" => TransferHotspotV1,
+    "transfer_hotspot_v2" => TransferHotspotV2,
+    "transfer_validator_stake_v1" => TransferValidatorStakeV1,
+    "unstake_validator_v1" => UnstakeValidatorV1,
+    "update_gateway_oui_v1" => UpdateGatewayOuiV1,
+    "update_subnetwork_v1" => UpdateSubnetworkV1,
+    "validator_heartbeat_v1" => ValidatorHeartbeatV1,
+    "vars_v1" => VarsV1,
+}
+
+impl fmt::Display for TransactionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Fields that all transactions have, or that are common to many of them.
+///
+/// This is implemented by `Transaction` and by the model of every supported
+/// transaction, so indexers can handle transactions without matching on
+/// every type. For an unsupported transaction the fee, nonce and payer are
+/// read from its JSON, if present.
+pub trait TransactionInfo {
+    /// The hash of the transaction
+    fn hash(&self) -> &str;
+
+    /// The type of the transaction, like `payment_v2`
+    fn type_name(&self) -> &str;
+
+    /// The fee of the transaction in data credits, if it has one
+    fn fee(&self) -> Option<u64> {
+        None
+    }
+
+    /// The nonce of the transaction, if it has one
+    fn nonce(&self) -> Option<u64> {
+        None
+    }
+
+    /// The address that paid for the transaction, if it names one
+    fn payer(&self) -> Option<&str> {
+        None
+    }
+
+    /// All addresses that take part in the transaction, with their role. An
+    /// address is listed once for every role it has.
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![]
+    }
+}
+
+/// The role of an address in a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AddressRole {
+    /// The account paying for the transaction
+    Payer,
+    /// An account receiving tokens
+    Payee,
+    /// The owner of a hotspot, validator, OUI or state channel
+    Owner,
+    /// The owner a hotspot or validator is transferred to
+    NewOwner,
+    /// A hotspot
+    Gateway,
+    /// A validator
+    Validator,
+    /// The seller of a hotspot
+    Seller,
+    /// The buyer of a hotspot
+    Buyer,
+    /// The hotspot issuing a proof of coverage challenge
+    Challenger,
+    /// A hotspot challenged in a proof of coverage
+    Challengee,
+    /// A hotspot witnessing a proof of coverage challenge
+    Witness,
+    /// A member of the consensus group
+    ConsensusMember,
+    /// The closer of a state channel
+    Closer,
+    /// A price oracle
+    Oracle,
+}
+
+/// A transaction together with the block it is part of.
+///
+/// Returned by all functions that list or get transactions, so they can be
+/// ordered and dated without looking up their blocks.
+#[derive(Clone, Debug)]
+pub struct TransactionEnvelope {
+    /// The height of the block containing the transaction
+    pub height: u64,
+    /// The time of the block containing the transaction, in seconds since the
+    /// Unix epoch
+    pub time: u64,
+    /// The hash of the block containing the transaction, if it was requested
+    /// by block hash. Serialized as `envelope_block_hash`, since proof of
+    /// coverage transactions have a `block_hash` field of their own.
+    pub block_hash: Option<String>,
+    pub txn: Transaction,
+}
+
+const ENVELOPE_BLOCK_HASH: &str = "envelope_block_hash";
+
+impl<'de> Deserialize<'de> for TransactionEnvelope {
+    fn deserialize<D>(d: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut raw = Value::deserialize(d)?;
+        let field = |name| match raw.get(name) {
+            Some(value) => u64::deserialize(value).map_err(D::Error::custom),
+            None => Err(D::Error::missing_field(name)),
+        };
+        let height = field("height")?;
+        let time = field("time")?;
+        let block_hash = match raw.as_object_mut() {
+            Some(fields) => fields.remove(ENVELOPE_BLOCK_HASH),
+            None => None,
+        };
+        let block_hash = match block_hash {
+            Some(value) => Option::<String>::deserialize(value).map_err(D::Error::custom)?,
+            None => None,
+        };
+        let txn = Transaction::from_value(raw).map_err(D::Error::custom)?;
+        Ok(Self {
+            height,
+            time,
+            block_hash,
+            txn,
+        })
+    }
+}
+
+impl Serialize for TransactionEnvelope {
+    fn serialize<S>(&self, s: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::Error as _;
+        let mut json = serde_json::to_value(&self.txn).map_err(S::Error::custom)?;
+        if let Value::Object(fields) = &mut json {
+            fields.insert("height".to_string(), self.height.into());
+            fields.insert("time".to_string(), self.time.into());
+            if let Some(block_hash) = &self.block_hash {
+                fields.insert(ENVELOPE_BLOCK_HASH.to_string(), block_hash.as_str().into());
+            }
+        }
+        json.serialize(s)
+    }
+}
+
+impl std::ops::Deref for TransactionEnvelope {
+    type Target = Transaction;
+
+    fn deref(&self) -> &Transaction {
+        &self.txn
+    }
+}
+
+impl TransactionInfo for TransactionEnvelope {
+    fn hash(&self) -> &str {
+        self.txn.hash()
+    }
+
+    fn type_name(&self) -> &str {
+        self.txn.type_name()
+    }
+
+    fn fee(&self) -> Option<u64> {
+        self.txn.fee()
+    }
+
+    fn nonce(&self) -> Option<u64> {
+        self.txn.nonce()
+    }
+
+    fn payer(&self) -> Option<&str> {
+        self.txn.payer()
+    }
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        self.txn.involved_addresses()
+    }
+}
+
+/// A transaction of a type that is not supported by this crate yet. The
+/// complete transaction is kept so it can be stored and processed later.
+#[derive(Clone, Debug)]
+pub struct UnknownTransaction {
+    /// The type of the transaction
+    pub type_name: String,
+    /// The hash of the transaction
+    pub hash: String,
+    /// The transaction as received from the API, including the `type`
+    pub raw: Value,
+}
+
+impl<'de> Deserialize<'de> for Transaction {
+    fn deserialize<D>(d: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::from_value(Value::deserialize(d)?).map_err(D::Error::custom)
+    }
+}
+
+impl Transaction {
+    /// Decode a transaction from its JSON value, keeping transactions of an
+    /// unsupported type as `Unknown`.
+    fn from_value(raw: Value) -> serde_json::Result<Self> {
+        use serde_json::Error;
+        let type_name = match raw.get("type") {
+            Some(Value::String(type_name)) => type_name.clone(),
+            _ => return Err(Error::missing_field("type")),
+        };
+        if Self::is_supported(&type_name) {
+            return Transaction::deserialize(&raw);
+        }
+        let hash = match raw.get("hash") {
+            Some(Value::String(hash)) => hash.clone(),
+            _ => return Err(Error::missing_field("hash")),
+        };
+        Ok(Self::Unknown(UnknownTransaction {
+            type_name,
+            hash,
+            raw,
+        }))
+    }
 }
+
+impl Serialize for Transaction {
+    fn serialize<S>(&self, s: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Unknown(txn) => txn.raw.serialize(s),
+            txn => Transaction::serialize(txn, s),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::*;
+    use crate::{
+        models::{Iot, Mobile},
+        *,
+    };
+    use std::str::FromStr;
     use tokio::test;
 
     #[test]
@@ -127,13 +534,13 @@ mod test {
         let txn = transactions::get(&client, "1gidN7e6OKn405Fru_0sGhsqca3lTsrfGKrM4dwM_E8")
             .await
             .expect("PocRequestV1");
-        if let Transaction::PocRequestV1(poc) = txn {
+        if let Transaction::PocRequestV1(poc) = txn.txn {
             assert_eq!(
                 poc.block_hash,
                 "RS2mBvd_4pbKCglkkyMroDQekPNO0xDdYx6Te3HGDGg"
             )
         } else {
-            assert!(false)
+            panic!()
         }
     }
     #[test]
@@ -142,10 +549,10 @@ mod test {
         let txn = transactions::get(&client, "yh01SJk8dvyqb-BGXxkHFUuLi6wF1pfL0VEFStJUt-E")
             .await
             .expect("ConsensusGroupV1");
-        if let Transaction::ConsensusGroupV1(cg) = txn {
+        if let Transaction::ConsensusGroupV1(cg) = txn.txn {
             assert_eq!(cg.hash, "yh01SJk8dvyqb-BGXxkHFUuLi6wF1pfL0VEFStJUt-E")
         } else {
-            assert!(false)
+            panic!()
         }
     }
     #[test]
@@ -155,10 +562,10 @@ mod test {
         let txn = transactions::get(&client, "C_jJZLKBOv_gRQ6P6wEpZPiRVAjf44FOx1iHOFD4haA")
             .await
             .expect("PaymentV2");
-        if let Transaction::PaymentV2(p) = txn {
+        if let Transaction::PaymentV2(p) = txn.txn {
             assert_eq!(p.payments.len(), 1)
         } else {
-            assert!(false)
+            panic!()
         }
     }
     #[test]
@@ -167,10 +574,10 @@ mod test {
         let txn = transactions::get(&client, "8RaF-G4pvMVuIXfBYhdqNuIlFSEHPm_rC8TH-h4JYdE")
             .await
             .expect("PocReceipt");
-        if let Transaction::PocReceiptsV1(pr) = txn {
+        if let Transaction::PocReceiptsV1(pr) = txn.txn {
             assert_eq!(pr.hash, "8RaF-G4pvMVuIXfBYhdqNuIlFSEHPm_rC8TH-h4JYdE")
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
@@ -180,10 +587,10 @@ mod test {
         let txn = transactions::get(&client, "077Y_ArUR90ptUMAkiKPp-9NIZfqol5TgGFkoJZa_K8")
             .await
             .expect("PocReceipt");
-        if let Transaction::PocReceiptsV2(pr) = txn {
+        if let Transaction::PocReceiptsV2(pr) = txn.txn {
             assert_eq!(pr.hash, "077Y_ArUR90ptUMAkiKPp-9NIZfqol5TgGFkoJZa_K8")
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
@@ -193,13 +600,13 @@ mod test {
         let txn = transactions::get(&client, "iMSckt_hUcMFY_d7W-QOupY0MGq_g3-CC2dq3P-HWIw")
             .await
             .expect("PaymentV1");
-        if let Transaction::PaymentV1(p) = txn {
+        if let Transaction::PaymentV1(p) = txn.txn {
             assert_eq!(
                 p.payee,
                 "14YeKFGXE23yAdACj6hu5NWEcYzzKxptYbm5jHgzw9A1P1UQfMv"
             )
         } else {
-            assert!(false)
+            panic!()
         }
     }
     #[test]
@@ -208,10 +615,10 @@ mod test {
         let txn = transactions::get(&client, "X0HNRGZ1HAX51CR8qS6LTopAosjFkuaaKXl850IpNDE")
             .await
             .expect("RewardsV2");
-        if let Transaction::RewardsV2(r) = txn {
+        if let Transaction::RewardsV2(r) = txn.txn {
             assert_eq!(r.rewards.len(), 10138)
         } else {
-            assert!(false)
+            panic!()
         }
     }
     #[test]
@@ -220,10 +627,10 @@ mod test {
         let txn = transactions::get(&client, "_I16bycHeltuOo7eyqa4uhv2Bc7awcztZflyvRkVZ24")
             .await
             .expect("AssertLocationV1");
-        if let Transaction::AssertLocationV1(al) = txn {
+        if let Transaction::AssertLocationV1(al) = txn.txn {
             assert_eq!(al.hash, "_I16bycHeltuOo7eyqa4uhv2Bc7awcztZflyvRkVZ24")
         } else {
-            assert!(false)
+            panic!()
         }
     }
     #[test]
@@ -232,13 +639,13 @@ mod test {
         let txn = transactions::get(&client, "TfjRv733Q9FBQ1_unw1c9g5ewVmMBuyf7APuyxKEqrw")
             .await
             .expect("AssertLocationV2");
-        if let Transaction::AssertLocationV2(al) = txn {
+        if let Transaction::AssertLocationV2(al) = txn.txn {
             assert_eq!(
                 al.gateway,
                 "112WVxXCrCjiKmmDXLDUJuhYGEHMbXobUZe8oJQkHoMHEFa149a"
             )
         } else {
-            assert!(false)
+            panic!()
         }
     }
     #[test]
@@ -247,13 +654,13 @@ mod test {
         let txn = transactions::get(&client, "aoTggHSgaBAamuUUrXnY42jDZ5WUBxE0k-tshvfn35E")
             .await
             .expect("AddGatewayV1");
-        if let Transaction::AddGatewayV1(ag) = txn {
+        if let Transaction::AddGatewayV1(ag) = txn.txn {
             assert_eq!(
                 ag.gateway,
                 "112uuvztDziVQyLVvBxMsovsSPV5ZXkN6uQ5hrWSaWwV1oEZTZtd"
             )
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
@@ -263,13 +670,13 @@ mod test {
         let txn = transactions::get(&client, "ia3c386ZnlVJorvo60WtXEFxy_0w35ImoIdmnW5lpJ8")
             .await
             .expect("AddGatewayV1");
-        if let Transaction::AddGatewayV1(ag) = txn {
+        if let Transaction::AddGatewayV1(ag) = txn.txn {
             assert_eq!(
                 ag.gateway,
                 "11GPcDmZGniewReZCnpC3SM19Jcw1sQU8W8CUnd7TCh4A6RmEkV"
             )
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
@@ -279,13 +686,13 @@ mod test {
         let txn = transactions::get(&client, "vjtEQK0vn1w69fV3TMrlnN6L_qprsoWM_-7DVspmLL8")
             .await
             .expect("StateChannelCloseV1");
-        if let Transaction::StateChannelCloseV1(sc) = txn {
+        if let Transaction::StateChannelCloseV1(sc) = txn.txn {
             assert_eq!(
                 sc.closer,
                 "11QVeYckasapcrmqjZqtfGTjE154uHHUvYPPwW6EMwzrpsdr213"
             )
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
@@ -295,13 +702,14 @@ mod test {
         let txn = transactions::get(&client, "fSFua7A8G41K05QXAvJi5N2OB0QqmQ7xp7u-My4rYHc")
             .await
             .expect("TransferHotspotV1");
-        if let Transaction::TransferHotspotV1(th) = txn {
+        if let Transaction::TransferHotspotV1(th) = &txn.txn {
             assert_eq!(
                 th.seller,
                 "14mo9fFGKYFaWh7xscpDLg7misWcuU5xqR8mc8gHr4c43nDnzeX"
-            )
+            );
+            assert_eq!(txn.payer(), Some(th.buyer.as_str()));
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
@@ -313,7 +721,7 @@ mod test {
             .await
             .expect("RoutingV1_NewXor");
 
-        if let Transaction::RoutingV1(r) = txn {
+        if let Transaction::RoutingV1(r) = txn.txn {
             assert_eq!(r.oui, 12);
             assert_eq!(
                 r.owner,
@@ -322,10 +730,10 @@ mod test {
             if let RoutingAction::NewXor(nx) = r.action {
                 assert_eq!(nx.filter, "wVwCiewtCpELAAAAAAAAAAAAAAAAAAAAf2gAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA");
             } else {
-                assert!(false)
+                panic!()
             }
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
@@ -337,7 +745,11 @@ mod test {
             .await
             .expect("UnstakeValidatorv1");
 
-        if let Transaction::UnstakeValidatorV1(uv) = txn {
+        assert_eq!(
+            txn.payer(),
+            Some("13d1s8rRWdQH4uU2w7PgpyCAPVkwBA4pEZKjE6rEht1j2yXzCZY")
+        );
+        if let Transaction::UnstakeValidatorV1(uv) = txn.txn {
             assert_eq!(
                 uv.address,
                 "11cY9Ly5H3hU4Ai2k7G9niHLAxsKb1ragQYGLJ7E9vh4Vnx6Efb"
@@ -345,7 +757,336 @@ mod test {
             assert_eq!(uv.stake_amount, 1000000000000u64.into());
             assert_eq!(uv.fee, 35000);
         } else {
-            assert!(false)
+            panic!()
+        }
+    }
+
+    #[test]
+    async fn unknown() {
+        let json = serde_json::json!({
+            "type": "example_v1",
+            "hash": "xRlFg4rVfkjG7Qzo3i0Ha7xEHHP7jtkHJhodpNRgm7I",
+            "token_type": "iot"
+        });
+        let txn: Transaction = serde_json::from_value(json.clone()).expect("transaction");
+        assert_eq!(txn.hash(), "xRlFg4rVfkjG7Qzo3i0Ha7xEHHP7jtkHJhodpNRgm7I");
+        if let Transaction::Unknown(unknown) = &txn {
+            assert_eq!(unknown.type_name, "example_v1");
+            assert_eq!(unknown.raw["token_type"], "iot");
+        } else {
+            panic!()
+        }
+        assert_eq!(serde_json::to_value(&txn).expect("json"), json);
+
+        let json = serde_json::json!({"type": "payment_v1", "hash": "h"});
+        assert!(serde_json::from_value::<Transaction>(json).is_err());
+    }
+
+    #[test]
+    async fn envelope_block_hash() {
+        let json = serde_json::json!({
+            "type": "poc_request_v1",
+            "hash": "1gidN7e6OKn405Fru_0sGhsqca3lTsrfGKrM4dwM_E8",
+            "block_hash": "RS2mBvd_4pbKSA_pUpeZkXJ2kMmHBWwpfPKdEBmZgiE",
+            "challenger": "11VKaN7fEvDm6NaGhcZtNSU1KAQQmTSwuuJsYYEqzh8mSWkoEUd",
+            "fee": 0,
+            "onion_key_hash": "bH1m8xCO4PN7ej7xZKhOqQ0cKFTrS2kqp-WpUbu2HJw",
+            "secret_hash": "qj3Ec2Yw9T_SM7kRr1c3T0t4GlG1TXIYm6qbVYgSAfs",
+            "version": 2,
+            "height": 1378232,
+            "time": 1654250000,
+            "envelope_block_hash": "BogDArZ5QxbgSd4wLmCS8NRtRzwvCA5fGn1V2TtsYoU"
+        });
+        let txn: TransactionEnvelope = serde_json::from_value(json.clone()).expect("envelope");
+        assert_eq!(
+            txn.block_hash.as_deref(),
+            Some("BogDArZ5QxbgSd4wLmCS8NRtRzwvCA5fGn1V2TtsYoU")
+        );
+        if let Transaction::PocRequestV1(poc) = &txn.txn {
+            assert_eq!(
+                poc.block_hash,
+                "RS2mBvd_4pbKSA_pUpeZkXJ2kMmHBWwpfPKdEBmZgiE"
+            );
+        } else {
+            panic!()
+        }
+        assert_eq!(serde_json::to_value(&txn).expect("json"), json);
+    }
+
+    #[test]
+    async fn subnetwork_rewards_v1() {
+        let json = serde_json::json!({
+            "type": "subnetwork_rewards_v1",
+            "hash": "kUfj8d6ZLXvnrDlwYFAjoHP2kxV2khmlZgEwoUHpqqA",
+            "token_type": "mobile",
+            "start_epoch": 1628130,
+            "end_epoch": 1628159,
+            "reward_server_signature": "",
+            "rewards": [
+                {"account": "14mo9fFGKYFaWh7xscpDLg7misWcuU5xqR8mc8gHr4c43nDnzeX", "amount": 150000000}
+            ]
+        });
+        let txn: Transaction = serde_json::from_value(json.clone()).expect("transaction");
+        if let Transaction::SubnetworkRewardsV1(rewards) = &txn {
+            assert_eq!(rewards.token_type, SubnetworkTokenType::Mobile);
+            assert_eq!(
+                rewards.rewards[0].amount,
+                SubnetworkAmount::Mobile(Mobile::from_str("1.5").expect("MOBILE"))
+            );
+        } else {
+            panic!()
+        }
+        assert_eq!(serde_json::to_value(&txn).expect("json"), json);
+    }
+
+    #[test]
+    async fn subnetwork_reward() {
+        let json = serde_json::json!({
+            "account": "14mo9fFGKYFaWh7xscpDLg7misWcuU5xqR8mc8gHr4c43nDnzeX",
+            "token_type": "iot",
+            "amount": 150000000
+        });
+        let reward: SubnetworkReward = serde_json::from_value(json).expect("reward");
+        assert_eq!(
+            reward.amount,
+            SubnetworkAmount::Iot(Iot::from_str("1.5").expect("IOT"))
+        );
+
+        let json = serde_json::json!({
+            "account": "14mo9fFGKYFaWh7xscpDLg7misWcuU5xqR8mc8gHr4c43nDnzeX",
+            "amount": 150000000
+        });
+        assert!(serde_json::from_value::<SubnetworkReward>(json).is_err());
+    }
+
+    #[test]
+    async fn add_subnetwork_v1() {
+        let json = serde_json::json!({
+            "type": "add_subnetwork_v1",
+            "hash": "qA1kCrQNSj9Xk2nqCUrmx0D4Mk6n5MxQ-JEchtPGhcE",
+            "token_type": "iot",
+            "subnetwork_key": "14v7NKS2dX5pQXUCpPsBQkHSTYuZ2qoVnKmcCEq8pDWSHsEWbWt",
+            "reward_server_keys": ["13FU5V5VJQU7Hhd1CoGBvG5CQkVWhDbkuxjw8WeD6DoGBhyyN6Z"],
+            "premine": 500000000000000u64
+        });
+        let txn: Transaction = serde_json::from_value(json.clone()).expect("transaction");
+        if let Transaction::AddSubnetworkV1(add) = &txn {
+            assert_eq!(add.token_type, SubnetworkTokenType::Iot);
+            assert_eq!(
+                add.premine,
+                SubnetworkAmount::Iot(Iot::from(500000000000000u64))
+            );
+            assert_eq!(add.reward_server_keys.len(), 1);
+        } else {
+            panic!()
+        }
+        assert_eq!(serde_json::to_value(&txn).expect("json"), json);
+    }
+
+    #[test]
+    async fn subnetwork_fund_v1() {
+        let json = serde_json::json!({
+            "type": "subnetwork_fund_v1",
+            "hash": "ZzE1UXQ3bT6bvIQ2eOoK6dM3_lq9cX3Yk4D7v0hC0cE",
+            "fee": 35000,
+            "nonce": 12,
+            "payer": "13buBykFQf5VaQtv7mWj2PBY9Lq4i1DeXhg7C4Vbu3ppzqqNkTH",
+            "token_type": "mobile",
+            "amount": 250000000
+        });
+        let txn: Transaction = serde_json::from_value(json.clone()).expect("transaction");
+        if let Transaction::SubnetworkFundV1(fund) = &txn {
+            assert_eq!(
+                fund.amount,
+                SubnetworkAmount::Mobile(Mobile::from_str("2.5").expect("MOBILE"))
+            );
+            assert_eq!(txn.payer(), Some(fund.payer.as_str()));
+            assert_eq!(txn.nonce(), Some(12));
+        } else {
+            panic!()
+        }
+        assert_eq!(serde_json::to_value(&txn).expect("json"), json);
+    }
+
+    #[test]
+    async fn update_subnetwork_v1() {
+        let json = serde_json::json!({
+            "type": "update_subnetwork_v1",
+            "hash": "3Ewm3oX2iE1QZ0y4Yb9kI3Wf4o8wY6cQkzjqZ4cfK2U",
+            "token_type": "mobile",
+            "nonce": 2,
+            "reward_server_keys_to_add": ["13FU5V5VJQU7Hhd1CoGBvG5CQkVWhDbkuxjw8WeD6DoGBhyyN6Z"],
+            "reward_server_keys_to_remove": []
+        });
+        let txn: Transaction = serde_json::from_value(json.clone()).expect("transaction");
+        if let Transaction::UpdateSubnetworkV1(update) = &txn {
+            assert_eq!(update.token_type, SubnetworkTokenType::Mobile);
+            assert_eq!(update.reward_server_keys_to_add.len(), 1);
+            assert!(update.reward_server_keys_to_remove.is_empty());
+        } else {
+            panic!()
+        }
+        assert_eq!(serde_json::to_value(&txn).expect("json"), json);
+    }
+
+    #[test]
+    async fn gen_validator_v1() {
+        let json = serde_json::json!({
+            "type": "gen_validator_v1",
+            "hash": "n2UaW8cuM4pJ1xOQ5CB2QWVh3Xx7mH9Qe2yTjYtSgLc",
+            "address": "11cY9Ly5H3hU4Ai2k7G9niHLAxsKb1ragQYGLJ7E9vh4Vnx6Efb",
+            "owner": "14mo9fFGKYFaWh7xscpDLg7misWcuU5xqR8mc8gHr4c43nDnzeX",
+            "stake": 1000000000000u64
+        });
+        let txn: Transaction = serde_json::from_value(json.clone()).expect("transaction");
+        if let Transaction::GenValidatorV1(gen) = &txn {
+            assert_eq!(gen.stake, 1000000000000u64.into());
+            assert_eq!(
+                txn.involved_addresses(),
+                [
+                    (gen.owner.as_str(), AddressRole::Owner),
+                    (gen.address.as_str(), AddressRole::Validator)
+                ]
+            );
+        } else {
+            panic!()
+        }
+        assert_eq!(serde_json::to_value(&txn).expect("json"), json);
+    }
+
+    #[test]
+    async fn transfer_hotspot_v2() {
+        let json = serde_json::json!({
+            "type": "transfer_hotspot_v2",
+            "hash": "7cDr6vHk0aVJmOTtPsWQYp3Z1sL6m4iFqJbQO8EDQyk",
+            "fee": 55000,
+            "nonce": 1,
+            "gateway": "112qB3YaH5bZkCnKA5uRH7tBtGNv2Y5B4smv1jsmvGUzgKT71QpE",
+            "owner": "14mo9fFGKYFaWh7xscpDLg7misWcuU5xqR8mc8gHr4c43nDnzeX",
+            "new_owner": "13buBykFQf5VaQtv7mWj2PBY9Lq4i1DeXhg7C4Vbu3ppzqqNkTH"
+        });
+        let txn: Transaction = serde_json::from_value(json.clone()).expect("transaction");
+        if let Transaction::TransferHotspotV2(transfer) = &txn {
+            assert_eq!(txn.fee(), Some(55000));
+            assert_eq!(
+                txn.involved_addresses(),
+                [
+                    (transfer.owner.as_str(), AddressRole::Owner),
+                    (transfer.new_owner.as_str(), AddressRole::NewOwner),
+                    (transfer.gateway.as_str(), AddressRole::Gateway)
+                ]
+            );
+        } else {
+            panic!()
+        }
+        assert_eq!(serde_json::to_value(&txn).expect("json"), json);
+    }
+
+    #[test]
+    async fn token_redeem_v1() {
+        let json = serde_json::json!({
+            "type": "token_redeem_v1",
+            "hash": "0p6ZmS6rB9kXKxLPC8k2vTAAMFQ4PqmGgthXHIt0kxA",
+            "fee": 35000,
+            "nonce": 3,
+            "account": "14mo9fFGKYFaWh7xscpDLg7misWcuU5xqR8mc8gHr4c43nDnzeX",
+            "token_type": "iot",
+            "amount": 25
+        });
+        let txn: Transaction = serde_json::from_value(json).expect("transaction");
+        if let Transaction::TokenRedeemV1(redeem) = txn {
+            assert_eq!(redeem.amount, SubnetworkAmount::Iot(Iot::from(25u64)));
+            assert_eq!(redeem.amount.token_type(), SubnetworkTokenType::Iot);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    async fn transaction_info() {
+        let client = get_test_client();
+        let txn = transactions::get(&client, "iMSckt_hUcMFY_d7W-QOupY0MGq_g3-CC2dq3P-HWIw")
+            .await
+            .expect("PaymentV1");
+        assert_eq!(txn.hash(), "iMSckt_hUcMFY_d7W-QOupY0MGq_g3-CC2dq3P-HWIw");
+        assert_eq!(txn.type_name(), "payment_v1");
+        assert!(txn.fee().is_some());
+        assert!(txn.nonce().is_some());
+        let payer = txn.payer().expect("payer");
+        assert_eq!(
+            txn.involved_addresses(),
+            [
+                (payer, AddressRole::Payer),
+                (
+                    "14YeKFGXE23yAdACj6hu5NWEcYzzKxptYbm5jHgzw9A1P1UQfMv",
+                    AddressRole::Payee
+                )
+            ]
+        );
+
+        const OWNER: &str = "14C6tdqSXeTCjroZyCCnxVD63Tot2THRguG8tSsMMjPqAqtR1HY";
+        const NEW_OWNER: &str = "13WRNw4fmssJBvMqMnREwe1eCvUVXfnWXSXGcWXyVvAnQUF3D9R";
+        let expected = [
+            (
+                "z-qr-F2Y4kwZaC9GYpJahiunL7ZMjW2-n4VnoOHaFLM",
+                "transfer_hotspot_v2",
+                Some(55000),
+                Some(1),
+                Some(OWNER),
+            ),
+            (
+                "r6xw5jGHsA6PxfUNeiPu6ulW5yryjF7NJeHMZkgRzm4",
+                "stake_validator_v1",
+                Some(35000),
+                None,
+                Some(OWNER),
+            ),
+            (
+                "jhHv-pcFN1BIJuE0BgQiZT2zofBilbTK2gEmbq4CvRw",
+                "transfer_hotspot_v1",
+                Some(55000),
+                Some(7),
+                Some(NEW_OWNER),
+            ),
+            (
+                "cbNstB6FS96iu9SzeWFP7orqgBPkaOdTPL2vHwh6nrU",
+                "transfer_validator_stake_v1",
+                Some(55000),
+                None,
+                Some(OWNER),
+            ),
+            (
+                "TV7_V42WQqjjMwJcYAwg4E6mCq0vxitOfrdRyquoeyM",
+                "assert_location_v2",
+                Some(55000),
+                Some(2),
+                None,
+            ),
+            (
+                "tGgl5MmZnpxVW3LScR-KXdVSoXkLuaXcEtbF78ZCbAQ",
+                "gen_price_oracle_v1",
+                None,
+                None,
+                None,
+            ),
+            (
+                "sjpqhDnA3eVRWJPnyQweMjO4YW5jRHDyDcSSi882Cbw",
+                "example_v1",
+                Some(35000),
+                Some(4),
+                Some(OWNER),
+            ),
+        ];
+        for (hash, type_name, fee, nonce, payer) in expected {
+            let txn = transactions::get(&client, hash).await.expect(type_name);
+            assert_eq!(txn.hash(), hash);
+            assert_eq!(txn.type_name(), type_name);
+            assert_eq!(
+                (txn.fee(), txn.nonce(), txn.payer()),
+                (fee, nonce, payer),
+                "{}",
+                type_name
+            );
         }
     }
 
@@ -355,14 +1096,14 @@ mod test {
         let txn = transactions::get(&client, "SB47bwBKP3ud1KdASYAndxkoIhZCXgPtusLUIsS7Q2o")
             .await
             .expect("VarsV1");
-        if let Transaction::VarsV1(v) = txn {
+        if let Transaction::VarsV1(v) = txn.txn {
             assert_eq!(
                 v.proof,
                 "MEUCIAXq0Pi0bK_DutFRF7R7ItEVrdUW2rmY8Guut5bHRboxAiEA9-wrvs7z9QZNRCC7XTKm4sb1cpXFD6TGB8Re8GfOyyA"
             );
             assert!(v.vars.get("sc_max_actors").is_some())
         } else {
-            assert!(false)
+            panic!()
         }
     }
 }
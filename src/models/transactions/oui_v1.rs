@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -13,3 +14,14 @@ pub struct OuiV1 {
     pub staking_fee: Hnt,
     pub requested_subnet_size: u64,
 }
+
+transaction_info! {
+    OuiV1, "oui_v1", [fee, payer];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.owner.as_str(), AddressRole::Owner),
+            (&self.payer, AddressRole::Payer),
+        ]
+    }
+}
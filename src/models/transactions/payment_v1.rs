@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -10,3 +11,14 @@ pub struct PaymentV1 {
     pub payer: String,
     pub payee: String,
 }
+
+transaction_info! {
+    PaymentV1, "payment_v1", [fee, nonce, payer];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.payer.as_str(), AddressRole::Payer),
+            (&self.payee, AddressRole::Payee),
+        ]
+    }
+}
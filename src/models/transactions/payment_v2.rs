@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::{Hnt, Hst, Iot, Mobile};
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +21,18 @@ pub enum PaymentV2Payment {
     Mobile(MobilePayment),
 }
 
+impl PaymentV2Payment {
+    /// The address receiving the payment
+    pub fn payee(&self) -> &str {
+        match self {
+            Self::Iot(payment) => &payment.payee,
+            Self::Hnt(payment) => &payment.payee,
+            Self::Hst(payment) => &payment.payee,
+            Self::Mobile(payment) => &payment.payee,
+        }
+    }
+}
+
 macro_rules! payment {
     ($payment_type:ident, $token:ident) => {
         #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -93,6 +106,18 @@ impl<'de> Deserialize<'de> for PaymentV2Payment {
     }
 }
 
+transaction_info! {
+    PaymentV2, "payment_v2", [fee, nonce, payer];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        let mut addresses = vec![(self.payer.as_str(), AddressRole::Payer)];
+        for payment in &self.payments {
+            addresses.push((payment.payee(), AddressRole::Payee));
+        }
+        addresses
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,7 +139,7 @@ mod tests {
         if let PaymentV2Payment::Hst(hst) = hnt_payment {
             assert_eq!(hst.amount, Hst::from_str("1.6").expect("HST"));
         } else {
-            assert!(false)
+            panic!()
         }
     }
 
@@ -134,7 +159,7 @@ mod tests {
         if let PaymentV2Payment::Hnt(hnt) = hnt_payment {
             assert_eq!(hnt.amount, Hnt::from_str("1.6").expect("HNT"));
         } else {
-            assert!(false)
+            panic!()
         }
     }
 }
@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -43,3 +44,18 @@ pub struct Witness {
     pub snr: f64,
     pub timestamp: u64,
 }
+
+transaction_info! {
+    PocReceiptsV1, "poc_receipts_v1", [fee];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        let mut addresses = vec![(self.challenger.as_str(), AddressRole::Challenger)];
+        for element in &self.path {
+            addresses.push((&element.challengee, AddressRole::Challengee));
+            for witness in &element.witnesses {
+                addresses.push((&witness.gateway, AddressRole::Witness));
+            }
+        }
+        addresses
+    }
+}
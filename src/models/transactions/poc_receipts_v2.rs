@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -65,3 +66,22 @@ pub struct Witness {
     pub snr: f64,
     pub timestamp: u64,
 }
+
+transaction_info! {
+    PocReceiptsV2, "poc_receipts_v2", [fee];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        let mut addresses = vec![
+            (self.challenger.as_str(), AddressRole::Challenger),
+            (&self.challenger_owner, AddressRole::Owner),
+        ];
+        for element in &self.path {
+            addresses.push((&element.challengee, AddressRole::Challengee));
+            addresses.push((&element.challengee_owner, AddressRole::Owner));
+            for witness in &element.witnesses {
+                addresses.push((&witness.gateway, AddressRole::Witness));
+            }
+        }
+        addresses
+    }
+}
@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -10,3 +11,11 @@ pub struct PocRequestV1 {
     pub secret_hash: String,
     pub version: u64,
 }
+
+transaction_info! {
+    PocRequestV1, "poc_request_v1", [fee];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![(self.challenger.as_str(), AddressRole::Challenger)]
+    }
+}
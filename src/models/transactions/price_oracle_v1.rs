@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Usd;
 use serde::{Deserialize, Serialize};
 
@@ -10,3 +11,11 @@ pub struct PriceOracleV1 {
     pub public_key: String,
     pub block_height: u64,
 }
+
+transaction_info! {
+    PriceOracleV1, "price_oracle_v1", [fee];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![(self.public_key.as_str(), AddressRole::Oracle)]
+    }
+}
@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -8,3 +9,11 @@ pub struct RedeemHtlcV1 {
     pub address: String,
     pub preimage: String,
 }
+
+transaction_info! {
+    RedeemHtlcV1, "redeem_htlc_v1", [fee];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![(self.payee.as_str(), AddressRole::Payee)]
+    }
+}
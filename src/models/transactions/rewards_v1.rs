@@ -1,4 +1,4 @@
-use super::Reward;
+use super::{transaction_info, AddressRole, Reward};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -8,3 +8,20 @@ pub struct RewardsV1 {
     pub end_epoch: u64,
     pub rewards: Vec<Reward>,
 }
+
+transaction_info! {
+    RewardsV1, "rewards_v1", [];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        let mut addresses = vec![];
+        for reward in &self.rewards {
+            if let Some(account) = &reward.account {
+                addresses.push((account.as_str(), AddressRole::Payee));
+            }
+            if let Some(gateway) = &reward.gateway {
+                addresses.push((gateway.as_str(), AddressRole::Gateway));
+            }
+        }
+        addresses
+    }
+}
@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -39,3 +40,11 @@ pub struct UpdateRouters {
 pub struct RequestSubnet {
     pub requested_subnet_size: u64,
 }
+
+transaction_info! {
+    RoutingV1, "routing_v1", [fee, nonce];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![(self.owner.as_str(), AddressRole::Owner)]
+    }
+}
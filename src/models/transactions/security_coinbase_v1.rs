@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -7,3 +8,11 @@ pub struct SecurityCoinbaseV1 {
     pub payee: String,
     pub amount: Hnt,
 }
+
+transaction_info! {
+    SecurityCoinbaseV1, "security_coinbase_v1", [];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![(self.payee.as_str(), AddressRole::Payee)]
+    }
+}
@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -10,3 +11,14 @@ pub struct StakeValidatorV1 {
     pub stake: Hnt,
     pub owner_signature: String,
 }
+
+transaction_info! {
+    StakeValidatorV1, "stake_validator_v1", [fee, payer = owner];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.owner.as_str(), AddressRole::Owner),
+            (&self.address, AddressRole::Validator),
+        ]
+    }
+}
@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -25,3 +26,18 @@ pub struct StateChannelSummary {
     pub num_dcs: u64,
     pub client: String,
 }
+
+transaction_info! {
+    StateChannelCloseV1, "state_channel_close_v1", [];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        let mut addresses = vec![
+            (self.closer.as_str(), AddressRole::Closer),
+            (&self.state_channel.owner, AddressRole::Owner),
+        ];
+        for summary in &self.state_channel.summaries {
+            addresses.push((&summary.client, AddressRole::Gateway));
+        }
+        addresses
+    }
+}
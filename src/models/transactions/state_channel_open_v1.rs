@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -12,3 +13,11 @@ pub struct StateChannelOpenV1 {
     pub amount: Hnt,
     pub expire_within: u64,
 }
+
+transaction_info! {
+    StateChannelOpenV1, "state_channel_open_v1", [fee, nonce];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![(self.owner.as_str(), AddressRole::Owner)]
+    }
+}
@@ -0,0 +1,25 @@
+use super::{
+    subnetwork_amounts, transaction_info, AddressRole, SubnetworkAmount, SubnetworkTokenType,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(remote = "Self")]
+pub struct SubnetworkFundV1 {
+    pub hash: String,
+    pub fee: u64,
+    pub nonce: u64,
+    pub payer: String,
+    pub token_type: SubnetworkTokenType,
+    pub amount: SubnetworkAmount,
+}
+
+subnetwork_amounts!(SubnetworkFundV1, ["amount"]);
+
+transaction_info! {
+    SubnetworkFundV1, "subnetwork_fund_v1", [fee, nonce, payer];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![(self.payer.as_str(), AddressRole::Payer)]
+    }
+}
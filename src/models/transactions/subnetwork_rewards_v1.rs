@@ -0,0 +1,57 @@
+use super::{
+    subnetwork_amounts, transaction_info, AddressRole, SubnetworkAmount, SubnetworkTokenType,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(remote = "Self")]
+pub struct SubnetworkRewardsV1 {
+    pub hash: String,
+    pub token_type: SubnetworkTokenType,
+    pub start_epoch: u64,
+    pub end_epoch: u64,
+    pub reward_server_signature: String,
+    pub rewards: Vec<SubnetworkReward>,
+}
+
+subnetwork_amounts!(SubnetworkRewardsV1, ["rewards"]);
+
+/// A reward paid by a subnetwork. On its own a reward is deserialized with
+/// the `token_type` of its amount, which a `SubnetworkRewardsV1` passes on
+/// to each of its rewards.
+#[derive(Clone, Serialize, Debug)]
+pub struct SubnetworkReward {
+    pub account: String,
+    pub amount: SubnetworkAmount,
+}
+
+impl<'de> Deserialize<'de> for SubnetworkReward {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Inner {
+            pub account: String,
+            pub token_type: SubnetworkTokenType,
+            pub amount: u64,
+        }
+
+        let i: Inner = Inner::deserialize(deserializer)?;
+        Ok(Self {
+            account: i.account,
+            amount: SubnetworkAmount::new(i.token_type, i.amount),
+        })
+    }
+}
+
+transaction_info! {
+    SubnetworkRewardsV1, "subnetwork_rewards_v1", [];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        self.rewards
+            .iter()
+            .map(|reward| (reward.account.as_str(), AddressRole::Payee))
+            .collect()
+    }
+}
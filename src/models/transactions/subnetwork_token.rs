@@ -0,0 +1,133 @@
+use crate::models::{Iot, Mobile};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+/// The token of a subnetwork.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SubnetworkTokenType {
+    Iot,
+    Mobile,
+}
+
+/// An amount of the token of a subnetwork. The token is given by the
+/// `token_type` of the transaction the amount is part of.
+///
+/// An amount is serialized as a number in the smallest unit of its token. On
+/// its own it is deserialized from an object with the `token_type` and the
+/// `amount`, the way a `PaymentV2Payment` carries its token type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubnetworkAmount {
+    Iot(Iot),
+    Mobile(Mobile),
+}
+
+impl SubnetworkAmount {
+    /// Construct an amount from its value in the smallest unit of the given
+    /// token.
+    pub fn new(token_type: SubnetworkTokenType, amount: u64) -> Self {
+        match token_type {
+            SubnetworkTokenType::Iot => Self::Iot(Iot::from(amount)),
+            SubnetworkTokenType::Mobile => Self::Mobile(Mobile::from(amount)),
+        }
+    }
+
+    pub fn token_type(&self) -> SubnetworkTokenType {
+        match self {
+            Self::Iot(_) => SubnetworkTokenType::Iot,
+            Self::Mobile(_) => SubnetworkTokenType::Mobile,
+        }
+    }
+}
+
+impl Serialize for SubnetworkAmount {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Iot(amount) => amount.serialize(s),
+            Self::Mobile(amount) => amount.serialize(s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SubnetworkAmount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Inner {
+            pub token_type: SubnetworkTokenType,
+            pub amount: u64,
+        }
+
+        let i: Inner = Inner::deserialize(deserializer)?;
+        Ok(Self::new(i.token_type, i.amount))
+    }
+}
+
+/// Pass the `token_type` of a transaction on to the given fields holding
+/// its amounts, so they can be deserialized on their own. A number is paired
+/// with the token type into a `SubnetworkAmount`, and the token type is added
+/// to each object in a list, like the entries of a `SubnetworkReward` list.
+pub(super) fn with_token_type(mut value: Value, fields: &[&str]) -> Value {
+    let token_type = match value.get("token_type") {
+        Some(token_type) => token_type.clone(),
+        None => return value,
+    };
+    if let Value::Object(txn) = &mut value {
+        for field in fields {
+            match txn.get_mut(*field) {
+                Some(Value::Array(entries)) => {
+                    for entry in entries {
+                        if let Value::Object(entry) = entry {
+                            entry
+                                .entry("token_type")
+                                .or_insert_with(|| token_type.clone());
+                        }
+                    }
+                }
+                Some(amount) => {
+                    *amount = serde_json::json!({
+                        "token_type": token_type,
+                        "amount": amount.take(),
+                    });
+                }
+                None => (),
+            }
+        }
+    }
+    value
+}
+
+/// Implement `Serialize` and `Deserialize` for a transaction with amounts in
+/// the token given by its `token_type`. The transaction derives both with
+/// `#[serde(remote = "Self")]`, and the given fields receive the token type
+/// before it is deserialized.
+macro_rules! subnetwork_amounts {
+    ($txn:ident, [$($field:literal),* $(,)?]) => {
+        impl<'de> serde::Deserialize<'de> for $txn {
+            fn deserialize<D>(d: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                use serde::de::Error;
+                let value = serde_json::Value::deserialize(d)?;
+                let value = super::subnetwork_token::with_token_type(value, &[$($field),*]);
+                $txn::deserialize(value).map_err(D::Error::custom)
+            }
+        }
+
+        impl serde::Serialize for $txn {
+            fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                $txn::serialize(self, s)
+            }
+        }
+    };
+}
+pub(super) use subnetwork_amounts;
@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -11,3 +12,14 @@ pub struct TokenBurnV1 {
     pub payer: String,
     pub amount: Hnt,
 }
+
+transaction_info! {
+    TokenBurnV1, "token_burn_v1", [fee, nonce, payer];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.payer.as_str(), AddressRole::Payer),
+            (&self.payee, AddressRole::Payee),
+        ]
+    }
+}
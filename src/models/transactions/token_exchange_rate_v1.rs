@@ -1,3 +1,4 @@
+use super::transaction_info;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -5,3 +6,5 @@ pub struct TokenBurnExchangeRateV1 {
     pub hash: String,
     pub rate: u64,
 }
+
+transaction_info!(TokenBurnExchangeRateV1, "token_burn_exchange_rate_v1", []);
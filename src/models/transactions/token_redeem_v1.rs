@@ -0,0 +1,25 @@
+use super::{
+    subnetwork_amounts, transaction_info, AddressRole, SubnetworkAmount, SubnetworkTokenType,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(remote = "Self")]
+pub struct TokenRedeemV1 {
+    pub hash: String,
+    pub fee: u64,
+    pub nonce: u64,
+    pub account: String,
+    pub token_type: SubnetworkTokenType,
+    pub amount: SubnetworkAmount,
+}
+
+subnetwork_amounts!(TokenRedeemV1, ["amount"]);
+
+transaction_info! {
+    TokenRedeemV1, "token_redeem_v1", [fee, nonce, payer = account];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![(self.account.as_str(), AddressRole::Payer)]
+    }
+}
@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -11,3 +12,15 @@ pub struct TransferHotspotV1 {
     pub buyer_nonce: u64,
     pub amount_to_seller: Hnt,
 }
+
+transaction_info! {
+    TransferHotspotV1, "transfer_hotspot_v1", [fee, nonce = buyer_nonce, payer = buyer];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.seller.as_str(), AddressRole::Seller),
+            (&self.buyer, AddressRole::Buyer),
+            (&self.gateway, AddressRole::Gateway),
+        ]
+    }
+}
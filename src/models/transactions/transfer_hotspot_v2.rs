@@ -0,0 +1,24 @@
+use super::{transaction_info, AddressRole};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TransferHotspotV2 {
+    pub hash: String,
+    pub fee: u64,
+    pub nonce: u64,
+    pub gateway: String,
+    pub owner: String,
+    pub new_owner: String,
+}
+
+transaction_info! {
+    TransferHotspotV2, "transfer_hotspot_v2", [fee, nonce, payer = owner];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.owner.as_str(), AddressRole::Owner),
+            (&self.new_owner, AddressRole::NewOwner),
+            (&self.gateway, AddressRole::Gateway),
+        ]
+    }
+}
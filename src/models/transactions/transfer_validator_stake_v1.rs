@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -14,3 +15,16 @@ pub struct TransferValidatorStakeV1 {
     pub payment_amount: Hnt,
     pub stake_amount: Hnt,
 }
+
+transaction_info! {
+    TransferValidatorStakeV1, "transfer_validator_stake_v1", [fee, payer = old_owner];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.old_owner.as_str(), AddressRole::Owner),
+            (&self.new_owner, AddressRole::NewOwner),
+            (&self.old_address, AddressRole::Validator),
+            (&self.new_address, AddressRole::Validator),
+        ]
+    }
+}
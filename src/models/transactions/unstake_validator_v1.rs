@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use crate::models::Hnt;
 use serde::{Deserialize, Serialize};
 
@@ -11,3 +12,14 @@ pub struct UnstakeValidatorV1 {
     pub stake_release_height: u64,
     pub hash: String,
 }
+
+transaction_info! {
+    UnstakeValidatorV1, "unstake_validator_v1", [fee, payer = owner];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![
+            (self.owner.as_str(), AddressRole::Owner),
+            (&self.address, AddressRole::Validator),
+        ]
+    }
+}
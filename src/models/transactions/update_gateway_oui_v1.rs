@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -10,3 +11,11 @@ pub struct UpdateGatewayOuiV1 {
     pub gateway_owner_signature: String,
     pub oui_owner_signature: String,
 }
+
+transaction_info! {
+    UpdateGatewayOuiV1, "update_gateway_oui_v1", [fee, nonce];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![(self.gateway.as_str(), AddressRole::Gateway)]
+    }
+}
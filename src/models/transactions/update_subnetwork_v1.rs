@@ -0,0 +1,15 @@
+use super::{transaction_info, SubnetworkTokenType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct UpdateSubnetworkV1 {
+    pub hash: String,
+    pub token_type: SubnetworkTokenType,
+    pub nonce: u64,
+    #[serde(default)]
+    pub reward_server_keys_to_add: Vec<String>,
+    #[serde(default)]
+    pub reward_server_keys_to_remove: Vec<String>,
+}
+
+transaction_info!(UpdateSubnetworkV1, "update_subnetwork_v1", [nonce]);
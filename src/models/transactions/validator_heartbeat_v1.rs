@@ -1,3 +1,4 @@
+use super::{transaction_info, AddressRole};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -8,3 +9,11 @@ pub struct ValidatorHeartbeatV1 {
     pub signature: String,
     pub version: u64,
 }
+
+transaction_info! {
+    ValidatorHeartbeatV1, "validator_heartbeat_v1", [];
+
+    fn involved_addresses(&self) -> Vec<(&str, AddressRole)> {
+        vec![(self.address.as_str(), AddressRole::Validator)]
+    }
+}
@@ -1,3 +1,4 @@
+use super::transaction_info;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -12,3 +13,5 @@ pub struct VarsV1 {
     pub master_key: Option<String>,
     pub key_proof: String,
 }
+
+transaction_info!(VarsV1, "vars_v1", [nonce]);
@@ -0,0 +1,312 @@
+//! Hooks to observe the requests issued by a `Client`.
+//!
+//! A `RequestObserver` set with `ClientBuilder::observer` is told about every
+//! attempt to send a request and about every response that was received and
+//! decoded, including the number of entries and cursor of each page of a
+//! paged request. This is enough to feed request counters, latency and size
+//! histograms of a metrics system.
+//!
+//! With the `tracing` feature every request is also wrapped in a `request`
+//! span carrying the method, path, status, latency, body size, page size,
+//! whether a cursor was returned and the total time spent waiting for the
+//! rate limiter, with an event for every failed attempt.
+use crate::Error;
+use reqwest::{Method, StatusCode};
+use std::{
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Receives events for the requests issued by a `Client`.
+///
+/// All methods have empty default implementations, so implementations only
+/// need to handle the events they are interested in.
+pub trait RequestObserver: fmt::Debug + Send + Sync {
+    /// Called after every attempt to send a request, including retries and
+    /// attempts on failover endpoints.
+    fn on_attempt(&self, _event: &AttemptEvent) {}
+
+    /// Called once a request completed, successfully or not.
+    fn on_response(&self, _event: &ResponseEvent) {}
+}
+
+impl<T: RequestObserver + ?Sized> RequestObserver for Arc<T> {
+    fn on_attempt(&self, event: &AttemptEvent) {
+        (**self).on_attempt(event)
+    }
+
+    fn on_response(&self, event: &ResponseEvent) {
+        (**self).on_response(event)
+    }
+}
+
+/// An attempt to send a request.
+#[derive(Debug)]
+pub struct AttemptEvent<'a> {
+    /// The request method
+    pub method: &'a Method,
+    /// The base URL the attempt was sent to
+    pub base_url: &'a str,
+    /// The API path relative to the base URL
+    pub path: &'a str,
+    /// The attempt number, starting at 1
+    pub attempt: u32,
+    /// The time the attempt waited for a permit of the rate limiter of the
+    /// client before it was sent
+    pub rate_limit_wait: Duration,
+    /// The response status, if a response was received
+    pub status: Option<StatusCode>,
+    /// The time until the response headers were received or the attempt
+    /// failed
+    pub latency: Duration,
+    /// The error the attempt failed with, if any
+    pub error: Option<&'a Error>,
+}
+
+/// A completed request.
+#[derive(Debug)]
+pub struct ResponseEvent<'a> {
+    /// The request method
+    pub method: &'a Method,
+    /// The API path relative to the base URL
+    pub path: &'a str,
+    /// The status of the final response, if one was received
+    pub status: Option<StatusCode>,
+    /// The total time of the request, including retries and receiving the
+    /// body
+    pub latency: Duration,
+    /// The size of the received body
+    pub bytes: usize,
+    /// The number of entries, if the response is a page of a paged request
+    pub page_size: Option<usize>,
+    /// Whether the response contained a cursor for a next page
+    pub has_cursor: bool,
+    /// The error the request failed with, if any
+    pub error: Option<&'a Error>,
+}
+
+/// Tracks a single request for the observer of a client and, with the
+/// `tracing` feature, its span.
+#[derive(Clone)]
+pub(crate) struct RequestSpan {
+    observer: Option<Arc<dyn RequestObserver>>,
+    method: Method,
+    path: String,
+    started: Instant,
+    /// The total time the attempts waited for the rate limiter, in
+    /// microseconds
+    #[cfg(feature = "tracing")]
+    rate_limit_wait: Arc<std::sync::atomic::AtomicU64>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl RequestSpan {
+    pub(crate) fn new(
+        observer: Option<Arc<dyn RequestObserver>>,
+        method: Method,
+        path: &str,
+    ) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "request",
+                method = %method,
+                path = %path,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                bytes = tracing::field::Empty,
+                page_size = tracing::field::Empty,
+                has_cursor = tracing::field::Empty,
+                rate_limit_wait_ms = tracing::field::Empty,
+            ),
+            observer,
+            method,
+            path: path.to_string(),
+            started: Instant::now(),
+            #[cfg(feature = "tracing")]
+            rate_limit_wait: Arc::default(),
+        }
+    }
+
+    /// Start timing the request. Requests are created before they are
+    /// polled, so this is called once the request is actually issued.
+    pub(crate) fn start(&mut self) {
+        self.started = Instant::now();
+    }
+
+    /// Run the given future within the tracing span of this request.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn instrument<F: std::future::Future>(
+        &self,
+        future: F,
+    ) -> impl std::future::Future<Output = F::Output> {
+        tracing::Instrument::instrument(future, self.span.clone())
+    }
+
+    /// Run the given future within the tracing span of this request.
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn instrument<F: std::future::Future>(&self, future: F) -> F {
+        future
+    }
+
+    pub(crate) fn attempt(
+        &self,
+        base_url: &str,
+        attempt: u32,
+        rate_limit_wait: Duration,
+        started: Instant,
+        status: Option<StatusCode>,
+        error: Option<&Error>,
+    ) {
+        let latency = started.elapsed();
+        #[cfg(feature = "tracing")]
+        {
+            use std::sync::atomic::Ordering;
+            let wait = rate_limit_wait.as_micros() as u64;
+            let total = self.rate_limit_wait.fetch_add(wait, Ordering::Relaxed) + wait;
+            self.span.record("rate_limit_wait_ms", total / 1000);
+        }
+        #[cfg(feature = "tracing")]
+        if let Some(error) = error {
+            tracing::warn!(
+                parent: &self.span,
+                base_url,
+                attempt,
+                status = status.map(|s| s.as_u16()),
+                latency_ms = latency.as_millis() as u64,
+                rate_limit_wait_ms = rate_limit_wait.as_millis() as u64,
+                %error,
+                "request attempt failed"
+            );
+        }
+        if let Some(observer) = &self.observer {
+            observer.on_attempt(&AttemptEvent {
+                method: &self.method,
+                base_url,
+                path: &self.path,
+                attempt,
+                rate_limit_wait,
+                status,
+                latency,
+                error,
+            });
+        }
+    }
+
+    pub(crate) fn finish(
+        &self,
+        status: Option<StatusCode>,
+        bytes: usize,
+        page_size: Option<usize>,
+        has_cursor: bool,
+        error: Option<&Error>,
+    ) {
+        let latency = self.started.elapsed();
+        #[cfg(feature = "tracing")]
+        {
+            if let Some(status) = status {
+                self.span.record("status", status.as_u16());
+            }
+            self.span.record("latency_ms", latency.as_millis() as u64);
+            self.span.record("bytes", bytes as u64);
+            if let Some(page_size) = page_size {
+                self.span.record("page_size", page_size as u64);
+            }
+            self.span.record("has_cursor", has_cursor);
+        }
+        if let Some(observer) = &self.observer {
+            observer.on_response(&ResponseEvent {
+                method: &self.method,
+                path: &self.path,
+                status,
+                latency,
+                bytes,
+                page_size,
+                has_cursor,
+                error,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{transport::MemoryTransport, Client, IntoVec, RetryPolicy, NO_QUERY};
+    use serde_json::json;
+    use std::sync::Mutex;
+    use tokio::test;
+
+    #[derive(Debug, Default)]
+    struct Events {
+        attempts: Mutex<Vec<(u32, Option<u16>)>>,
+        rate_limit_waits: Mutex<Vec<Duration>>,
+        responses: Mutex<Vec<(Option<usize>, bool, usize)>>,
+    }
+
+    impl RequestObserver for Events {
+        fn on_attempt(&self, event: &AttemptEvent) {
+            let status = event.status.map(|s| s.as_u16());
+            self.attempts.lock().unwrap().push((event.attempt, status));
+            let wait = event.rate_limit_wait;
+            self.rate_limit_waits.lock().unwrap().push(wait);
+        }
+
+        fn on_response(&self, event: &ResponseEvent) {
+            let response = (event.page_size, event.has_cursor, event.bytes);
+            self.responses.lock().unwrap().push(response);
+        }
+    }
+
+    #[test]
+    async fn observe_pages() {
+        let transport = MemoryTransport::new()
+            .with_status("/ouis", StatusCode::SERVICE_UNAVAILABLE)
+            .with_json("/ouis", json!({"data": [1, 2], "cursor": "a"}))
+            .with_json_query("/ouis", &[("cursor", "a")], json!({"data": [3]}));
+        let events = Arc::new(Events::default());
+        let client = Client::builder()
+            .transport(transport)
+            .retry_policy(RetryPolicy::default().initial_backoff(Duration::from_millis(1)))
+            .observer(events.clone())
+            .build()
+            .expect("client");
+        let entries: Vec<u64> = client
+            .fetch_stream("/ouis", NO_QUERY)
+            .into_vec()
+            .await
+            .expect("entries");
+        assert_eq!(entries, [1, 2, 3]);
+        assert_eq!(
+            *events.attempts.lock().unwrap(),
+            [(1, Some(503)), (2, Some(200)), (1, Some(200))]
+        );
+        assert_eq!(
+            *events.responses.lock().unwrap(),
+            [(Some(2), true, 27), (Some(1), false, 12)]
+        );
+    }
+
+    #[test]
+    async fn observe_rate_limit_wait() {
+        let transport = MemoryTransport::new().with_json("/blocks/height", json!({"data": 1}));
+        let events = Arc::new(Events::default());
+        let client = Client::builder()
+            .transport(transport)
+            .rate_limit(20.0, 1)
+            .observer(events.clone())
+            .build()
+            .expect("client");
+        for _ in 0..2 {
+            let _: u64 = client
+                .fetch("/blocks/height", NO_QUERY)
+                .await
+                .expect("height");
+        }
+        let waits = events.rate_limit_waits.lock().unwrap();
+        assert_eq!(waits[0], Duration::ZERO);
+        assert!(waits[1] > Duration::from_millis(20), "{:?}", waits[1]);
+    }
+}
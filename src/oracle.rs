@@ -11,6 +11,12 @@ pub mod prices {
         client.fetch_stream("/oracle/prices", NO_QUERY)
     }
 
+    /// Fetch all inferred oracle prices as pages, starting after the page for
+    /// the given cursor if any
+    pub fn all_pages(client: &Client, cursor: Option<&str>) -> Stream<Page<OraclePrice>> {
+        client.fetch_pages("/oracle/prices", NO_QUERY, cursor)
+    }
+
     /// Get the current valid oracle price
     pub async fn current(client: &Client) -> Result<OraclePrice> {
         client.fetch("/oracle/prices/current", NO_QUERY).await
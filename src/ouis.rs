@@ -8,6 +8,12 @@ pub fn all(client: &Client) -> Stream<Oui> {
     client.fetch_stream("/ouis", NO_QUERY)
 }
 
+/// Get a stream of all ouis as pages, starting after the page for the given
+/// cursor if any
+pub fn all_pages(client: &Client, cursor: Option<&str>) -> Stream<Page<Oui>> {
+    client.fetch_pages("/ouis", NO_QUERY, cursor)
+}
+
 /// Get a specific oui
 pub async fn get(client: &Client, oui: u64) -> Result<Oui> {
     client.fetch(&format!("/ouis/{}", oui), NO_QUERY).await
@@ -32,7 +38,7 @@ mod test {
     async fn all() {
         let client = get_test_client();
         let ouis = ouis::all(&client).into_vec().await.expect("ouis");
-        assert!(ouis.len() > 0);
+        assert!(!ouis.is_empty());
     }
 
     #[test]
@@ -1,4 +1,5 @@
 use crate::{models::transactions::PendingTxnStatus, *};
+use base64::Engine;
 use serde_json::json;
 
 /// Convert a given transaction to json, ready to be submitted
@@ -7,7 +8,7 @@ pub async fn submit<T>(client: &Client, txn: T) -> Result<PendingTxnStatus>
 where
     T: AsRef<[u8]>,
 {
-    let json = json!({ "txn": base64::encode(&txn) });
+    let json = json!({ "txn": base64::engine::general_purpose::STANDARD.encode(&txn) });
     client.post("/pending_transactions", &json).await
 }
 
@@ -0,0 +1,145 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A token bucket rate limiter for API requests.
+///
+/// The bucket holds up to `burst` permits and is refilled at
+/// `requests_per_second`. Every request issued by a `Client`, including each
+/// page fetched by a stream and each retry, takes one permit and waits for one
+/// to become available when the bucket is empty.
+///
+/// A limiter is cheap to clone and all clones share the same bucket. This
+/// includes the limiter of a `Client` and all clones of that client, so the
+/// configured rate applies to the aggregate of all of them.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    requests_per_second: f64,
+    burst: f64,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    tokens: f64,
+    refilled_at: Instant,
+    permits: u64,
+    waited: Duration,
+    last_wait: Duration,
+}
+
+/// Statistics on the permits handed out by a `RateLimiter`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RateLimitStats {
+    /// The number of permits handed out.
+    pub permits: u64,
+    /// The total time requests waited for a permit.
+    pub waited: Duration,
+    /// The time the most recent request waited for a permit.
+    pub last_wait: Duration,
+}
+
+impl RateLimiter {
+    /// Create a new limiter allowing `requests_per_second` on average with
+    /// bursts of up to `burst` requests. The bucket starts out full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `requests_per_second` is not a positive number.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        assert!(
+            requests_per_second > 0.0,
+            "requests_per_second must be positive"
+        );
+        let burst = f64::from(burst.max(1));
+        Self {
+            inner: Arc::new(Inner {
+                requests_per_second,
+                burst,
+                state: Mutex::new(State {
+                    tokens: burst,
+                    refilled_at: Instant::now(),
+                    permits: 0,
+                    waited: Duration::default(),
+                    last_wait: Duration::default(),
+                }),
+            }),
+        }
+    }
+
+    /// Wait for a permit to issue a request. Returns how long the caller had
+    /// to wait for the permit.
+    pub async fn acquire(&self) -> Duration {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        wait
+    }
+
+    /// Take a token from the bucket and return how long to wait before it is
+    /// valid. Tokens are reserved ahead of time when the bucket is empty,
+    /// which keeps waiting callers in order.
+    fn reserve(&self) -> Duration {
+        let mut state = self.inner.state.lock().expect("rate limiter state");
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+        state.tokens =
+            (state.tokens + elapsed * self.inner.requests_per_second).min(self.inner.burst);
+        state.refilled_at = now;
+        state.tokens -= 1.0;
+        let wait = if state.tokens >= 0.0 {
+            Duration::default()
+        } else {
+            Duration::from_secs_f64(-state.tokens / self.inner.requests_per_second)
+        };
+        state.permits += 1;
+        state.waited += wait;
+        state.last_wait = wait;
+        wait
+    }
+
+    /// Returns statistics on the permits handed out by this limiter and all
+    /// its clones.
+    pub fn stats(&self) -> RateLimitStats {
+        let state = self.inner.state.lock().expect("rate limiter state");
+        RateLimitStats {
+            permits: state.permits,
+            waited: state.waited,
+            last_wait: state.last_wait,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio::test;
+
+    #[test]
+    async fn burst() {
+        let limiter = RateLimiter::new(10.0, 2);
+        assert_eq!(limiter.acquire().await, Duration::default());
+        assert_eq!(limiter.acquire().await, Duration::default());
+        let wait = limiter.acquire().await;
+        assert!(wait > Duration::from_millis(50) && wait <= Duration::from_millis(100));
+        let stats = limiter.stats();
+        assert_eq!(stats.permits, 3);
+        assert_eq!(stats.last_wait, wait);
+    }
+
+    #[test]
+    async fn shared() {
+        let limiter = RateLimiter::new(10.0, 1);
+        let clone = limiter.clone();
+        assert_eq!(limiter.acquire().await, Duration::default());
+        assert!(clone.acquire().await > Duration::default());
+        assert_eq!(limiter.stats().permits, 2);
+    }
+}
@@ -0,0 +1,200 @@
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::time::Duration;
+
+/// The default maximum number of attempts for a request, including the first
+const DEFAULT_MAX_ATTEMPTS: u32 = 4;
+/// The default backoff before the first retry of a request
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// The default upper bound on the backoff between two attempts
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Describes how a `Client` retries requests that fail with a transient error.
+///
+/// Requests are retried when the API responds with `429 Too Many Requests` or
+/// any `5xx` status, or when connecting to or reading from the API times out.
+/// The backoff between attempts grows exponentially from `initial_backoff` up
+/// to `max_backoff`. With jitter enabled a random delay of up to half the
+/// backoff is taken off to avoid many clients retrying in lock step. When the
+/// API includes a `Retry-After` header that delay is used instead, up to
+/// `max_backoff`.
+///
+/// Only GET requests are retried by default. Submitting a transaction is
+/// retried only when `retry_post` is enabled.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_post: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            jitter: true,
+            retry_post: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries a request.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Set the maximum number of attempts for a request, including the first
+    /// one. A value of 0 is treated as 1.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the backoff before the first retry.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the upper bound on the backoff between two attempts.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Enable or disable random jitter on the backoff.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Enable or disable retrying POST requests, like transaction
+    /// submissions.
+    pub fn retry_post(mut self, retry_post: bool) -> Self {
+        self.retry_post = retry_post;
+        self
+    }
+
+    /// Returns the maximum number of attempts for a request, or 1 for POST
+    /// requests when those are not to be retried.
+    pub(crate) fn attempts(&self, is_post: bool) -> u32 {
+        if is_post && !self.retry_post {
+            1
+        } else {
+            self.max_attempts
+        }
+    }
+
+    /// Returns the delay before retrying the given failed attempt, starting
+    /// at 1. A delay requested by the API is used up to `max_backoff`.
+    pub(crate) fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        match retry_after {
+            Some(retry_after) => retry_after.min(self.max_backoff),
+            None => self.backoff(attempt),
+        }
+    }
+
+    /// Returns the backoff after the given failed attempt, starting at 1.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+        if self.jitter {
+            backoff.mul_f64(1.0 - rand::random::<f64>() / 2.0)
+        } else {
+            backoff
+        }
+    }
+}
+
+/// Parses the `Retry-After` header, given in either delay seconds or as an
+/// HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        date.with_timezone(&Utc)
+            .signed_duration_since(Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy::default()
+            .initial_backoff(Duration::from_secs(1))
+            .max_backoff(Duration::from_secs(5))
+            .jitter(false);
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4), Duration::from_secs(5));
+        assert_eq!(policy.backoff(64), Duration::from_secs(5));
+
+        let policy = policy.jitter(true);
+        let backoff = policy.backoff(2);
+        assert!(backoff >= Duration::from_secs(1) && backoff <= Duration::from_secs(2));
+    }
+
+    #[test]
+    fn delay() {
+        let policy = RetryPolicy::default()
+            .initial_backoff(Duration::from_secs(1))
+            .max_backoff(Duration::from_secs(5))
+            .jitter(false);
+        assert_eq!(policy.delay(2, None), Duration::from_secs(2));
+        assert_eq!(
+            policy.delay(2, Some(Duration::from_secs(3))),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            policy.delay(2, Some(Duration::from_secs(3600))),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn attempts() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.attempts(false), DEFAULT_MAX_ATTEMPTS);
+        assert_eq!(policy.attempts(true), 1);
+        assert_eq!(policy.retry_post(true).attempts(true), DEFAULT_MAX_ATTEMPTS);
+        assert_eq!(RetryPolicy::none().attempts(false), 1);
+    }
+
+    #[test]
+    fn parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(120)));
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(0)));
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(retry_after(&headers), None);
+    }
+}
@@ -1,7 +1,7 @@
-use crate::{models::transactions::Transaction, *};
+use crate::{models::transactions::TransactionEnvelope, *};
 
 /// Get a specific account by its address
-pub async fn get(client: &Client, hash: &str) -> Result<Transaction> {
+pub async fn get(client: &Client, hash: &str) -> Result<TransactionEnvelope> {
     client
         .fetch(&format!("/transactions/{}", hash), NO_QUERY)
         .await
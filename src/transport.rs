@@ -0,0 +1,436 @@
+//! The HTTP transport used by a `Client` to issue requests.
+//!
+//! By default a client sends requests with `reqwest` through a
+//! `ReqwestTransport`. Any other implementation of the `Transport` trait can
+//! be configured with `ClientBuilder::transport`, for example the
+//! `MemoryTransport` which serves canned responses without network access:
+//!
+//! ```
+//! use helium_api::{blocks, transport::MemoryTransport, Client};
+//! use serde_json::json;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> helium_api::Result {
+//! let transport =
+//!     MemoryTransport::new().with_json("/blocks/height", json!({"data": {"height": 42}}));
+//! let client = Client::builder().transport(transport).build()?;
+//! assert_eq!(blocks::height(&client).await?, 42);
+//! # Ok(())
+//! # }
+//! ```
+use crate::{Error, Result, Stream};
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures::{stream, StreamExt, TryStreamExt};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    Method, StatusCode,
+};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// Sends requests on behalf of a `Client`.
+///
+/// Implementations only need to deliver a request and hand back the response.
+/// Retries, rate limiting and the mapping of unsuccessful responses to errors
+/// are handled by the client.
+#[async_trait]
+pub trait Transport: fmt::Debug + Send + Sync {
+    async fn send(&self, request: Request) -> Result<Response>;
+}
+
+#[async_trait]
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    async fn send(&self, request: Request) -> Result<Response> {
+        (**self).send(request).await
+    }
+}
+
+/// A request issued by a `Client`.
+#[derive(Clone, Debug)]
+pub struct Request {
+    /// The request method
+    pub method: Method,
+    /// The base URL of the client
+    pub base_url: String,
+    /// The API path relative to the base URL, like `/blocks/height`
+    pub path: String,
+    /// The URL encoded query string, without a leading `?`
+    pub query: String,
+    /// The JSON encoded body, if any
+    pub body: Option<Bytes>,
+}
+
+impl Request {
+    /// Create a GET request for the given path and query.
+    pub fn get<Q>(base_url: &str, path: &str, query: &Q) -> Result<Self>
+    where
+        Q: Serialize + ?Sized,
+    {
+        Ok(Self {
+            method: Method::GET,
+            base_url: base_url.to_string(),
+            path: path.to_string(),
+            query: serde_urlencoded::to_string(query).map_err(Error::encode)?,
+            body: None,
+        })
+    }
+
+    /// Create a POST request for the given path with a JSON body.
+    pub fn post<T>(base_url: &str, path: &str, json: &T) -> Result<Self>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(Self {
+            method: Method::POST,
+            base_url: base_url.to_string(),
+            path: path.to_string(),
+            query: String::new(),
+            body: Some(serde_json::to_vec(json).map_err(Error::encode)?.into()),
+        })
+    }
+
+    /// The full URL of the request, including the query.
+    pub fn url(&self) -> String {
+        if self.query.is_empty() {
+            format!("{}{}", self.base_url, self.path)
+        } else {
+            format!("{}{}?{}", self.base_url, self.path, self.query)
+        }
+    }
+
+    /// The query of the request as decoded key/value pairs.
+    pub fn query_pairs(&self) -> Vec<(String, String)> {
+        serde_urlencoded::from_str(&self.query).unwrap_or_default()
+    }
+}
+
+/// A response to a `Request`. The body is a stream of chunks as they are
+/// received.
+pub struct Response {
+    /// The response status
+    pub status: StatusCode,
+    /// The response headers
+    pub headers: HeaderMap,
+    /// The URL the response was received from
+    pub url: String,
+    body: Stream<Bytes>,
+}
+
+impl fmt::Debug for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Response")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .field("url", &self.url)
+            .finish()
+    }
+}
+
+impl Response {
+    /// Create a response with a complete body.
+    pub fn new<B: Into<Bytes>>(status: StatusCode, url: &str, body: B) -> Self {
+        let body = body.into();
+        Self::from_stream(status, url, stream::once(async move { Ok(body) }).boxed())
+    }
+
+    /// Create a response with a body that is received in chunks.
+    pub fn from_stream(status: StatusCode, url: &str, body: Stream<Bytes>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            url: url.to_string(),
+            body,
+        }
+    }
+
+    /// Returns the stream of body chunks.
+    pub fn into_body(self) -> Stream<Bytes> {
+        self.body
+    }
+
+    /// Receives the complete body.
+    pub async fn bytes(self) -> Result<Bytes> {
+        let body = self
+            .body
+            .try_fold(BytesMut::new(), |mut body, chunk| async move {
+                body.extend_from_slice(&chunk);
+                Ok(body)
+            })
+            .await?;
+        Ok(body.freeze())
+    }
+
+    /// Receives the complete body as text, replacing invalid UTF-8.
+    pub async fn text(self) -> Result<String> {
+        let body = self.bytes().await?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+}
+
+/// The default transport which sends requests with a `reqwest::Client`.
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl From<reqwest::Client> for ReqwestTransport {
+    fn from(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: Request) -> Result<Response> {
+        let url = request.url();
+        let mut builder = self.client.request(request.method, &url);
+        if let Some(body) = request.body {
+            builder = builder
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .body(body);
+        }
+        let response = builder.send().await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let url = response.url().to_string();
+        let body = response.bytes_stream().map_err(Error::from).boxed();
+        let mut response = Response::from_stream(status, &url, body);
+        response.headers = headers;
+        Ok(response)
+    }
+}
+
+/// A transport that serves canned responses from memory.
+///
+/// Responses are registered for a method, path and optionally a query. A
+/// request is answered with the response registered for its exact query, or
+/// else the response registered for its path without a query. Requests that
+/// match no registered response are answered with `404 Not Found`.
+///
+/// When several responses are registered for the same request they are
+/// served in order, with the last one repeated for any further requests.
+/// This allows, for example, a transient error to be followed by a successful
+/// response.
+///
+/// The transport is cheap to clone and all clones share the same responses
+/// and request log.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryTransport {
+    inner: Arc<Mutex<MemoryState>>,
+}
+
+type RouteKey = (Method, String, Option<Vec<(String, String)>>);
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    routes: HashMap<RouteKey, Vec<MemoryResponse>>,
+    requests: Vec<Request>,
+}
+
+#[derive(Clone, Debug)]
+struct MemoryResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl MemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a response for the given method, path and optional query.
+    pub fn insert<Q, B>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<&Q>,
+        status: StatusCode,
+        headers: HeaderMap,
+        body: B,
+    ) -> Result
+    where
+        Q: Serialize + ?Sized,
+        B: Into<Bytes>,
+    {
+        let query = match query {
+            Some(query) => Some(sorted_pairs(
+                serde_urlencoded::from_str(
+                    &serde_urlencoded::to_string(query).map_err(Error::encode)?,
+                )
+                .unwrap_or_default(),
+            )),
+            None => None,
+        };
+        let response = MemoryResponse {
+            status,
+            headers,
+            body: body.into(),
+        };
+        self.inner
+            .lock()
+            .expect("memory transport state")
+            .routes
+            .entry((method, path.to_string(), query))
+            .or_default()
+            .push(response);
+        Ok(())
+    }
+
+    /// Serve the given JSON for GET requests to a path with any query.
+    pub fn with_json(self, path: &str, json: serde_json::Value) -> Self {
+        self.insert(
+            Method::GET,
+            path,
+            None::<&()>,
+            StatusCode::OK,
+            HeaderMap::new(),
+            json.to_string(),
+        )
+        .expect("route");
+        self
+    }
+
+    /// Serve the given JSON for GET requests to a path with the given query.
+    pub fn with_json_query<Q>(self, path: &str, query: &Q, json: serde_json::Value) -> Self
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.insert(
+            Method::GET,
+            path,
+            Some(query),
+            StatusCode::OK,
+            HeaderMap::new(),
+            json.to_string(),
+        )
+        .expect("route");
+        self
+    }
+
+    /// Serve the given JSON for POST requests to a path.
+    pub fn with_post_json(self, path: &str, json: serde_json::Value) -> Self {
+        self.insert(
+            Method::POST,
+            path,
+            None::<&()>,
+            StatusCode::OK,
+            HeaderMap::new(),
+            json.to_string(),
+        )
+        .expect("route");
+        self
+    }
+
+    /// Answer GET requests to a path with any query with the given status and
+    /// an empty body.
+    pub fn with_status(self, path: &str, status: StatusCode) -> Self {
+        self.insert(
+            Method::GET,
+            path,
+            None::<&()>,
+            status,
+            HeaderMap::new(),
+            Bytes::new(),
+        )
+        .expect("route");
+        self
+    }
+
+    /// Returns all requests received by this transport so far.
+    pub fn requests(&self) -> Vec<Request> {
+        self.inner
+            .lock()
+            .expect("memory transport state")
+            .requests
+            .clone()
+    }
+}
+
+#[async_trait]
+impl Transport for MemoryTransport {
+    async fn send(&self, request: Request) -> Result<Response> {
+        let url = request.url();
+        let mut state = self.inner.lock().expect("memory transport state");
+        state.requests.push(request.clone());
+        let query = Some(sorted_pairs(request.query_pairs()));
+        let exact = (request.method.clone(), request.path.clone(), query);
+        let any = (request.method, request.path, None);
+        let responses = match state.routes.get_mut(&exact) {
+            Some(responses) => Some(responses),
+            None => state.routes.get_mut(&any),
+        };
+        let response = match responses {
+            Some(responses) if responses.len() > 1 => responses.remove(0),
+            Some(responses) => responses[0].clone(),
+            None => MemoryResponse {
+                status: StatusCode::NOT_FOUND,
+                headers: HeaderMap::new(),
+                body: Bytes::new(),
+            },
+        };
+        let mut result = Response::new(response.status, &url, response.body);
+        result.headers = response.headers;
+        Ok(result)
+    }
+}
+
+fn sorted_pairs(mut pairs: Vec<(String, String)>) -> Vec<(String, String)> {
+    pairs.sort();
+    pairs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use tokio::test;
+
+    #[test]
+    async fn memory_routes() {
+        let transport = MemoryTransport::new()
+            .with_json("/accounts", json!({"data": [1]}))
+            .with_json_query("/accounts", &[("cursor", "abc")], json!({"data": [2]}));
+        let send = |query: &[(&str, &str)]| {
+            let request = Request::get("http://localhost", "/accounts", query).expect("request");
+            transport.send(request)
+        };
+        let body = send(&[]).await.expect("response").text().await;
+        assert_eq!(body.expect("body"), r#"{"data":[1]}"#);
+        let body = send(&[("cursor", "abc")])
+            .await
+            .expect("response")
+            .text()
+            .await;
+        assert_eq!(body.expect("body"), r#"{"data":[2]}"#);
+
+        let request = Request::get("http://localhost", "/hotspots", &[("a", 1)]).expect("request");
+        assert_eq!(request.url(), "http://localhost/hotspots?a=1");
+        let response = transport.send(request).await.expect("response");
+        assert_eq!(response.status, StatusCode::NOT_FOUND);
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[test]
+    async fn memory_sequence() {
+        let transport = MemoryTransport::new()
+            .with_status("/blocks/height", StatusCode::SERVICE_UNAVAILABLE)
+            .with_json("/blocks/height", json!({"data": {"height": 1}}));
+        let send = || {
+            let request = Request::get("http://localhost", "/blocks/height", crate::NO_QUERY)
+                .expect("request");
+            transport.send(request)
+        };
+        assert_eq!(
+            send().await.expect("response").status,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(send().await.expect("response").status, StatusCode::OK);
+        assert_eq!(send().await.expect("response").status, StatusCode::OK);
+    }
+}
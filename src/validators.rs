@@ -1,5 +1,7 @@
 use crate::{
-    models::{QueryTimeRange, Reward, Validator, ValidatorStats},
+    models::{
+        Bucket, QueryRewardSum, QueryTimeRange, Reward, RewardSums, Validator, ValidatorStats,
+    },
     *,
 };
 
@@ -8,6 +10,12 @@ pub fn all(client: &Client) -> Stream<Validator> {
     client.fetch_stream("/validators", NO_QUERY)
 }
 
+/// Get all known validators as pages, starting after the page for the given
+/// cursor if any
+pub fn all_pages(client: &Client, cursor: Option<&str>) -> Stream<Page<Validator>> {
+    client.fetch_pages("/validators", NO_QUERY, cursor)
+}
+
 /// Get a specific validator
 pub async fn get(client: &Client, address: &str) -> Result<Validator> {
     client
@@ -15,6 +23,24 @@ pub async fn get(client: &Client, address: &str) -> Result<Validator> {
         .await
 }
 
+/// Get the validators for the given addresses, with up to `concurrency` requests
+/// in flight, or one if `concurrency` is zero. Each result is returned with its
+/// address, in the order given by `order`, and a failed lookup does not end the
+/// stream.
+pub fn get_many<I, S>(
+    client: &Client,
+    addresses: I,
+    concurrency: usize,
+    order: Order,
+) -> Batch<Validator>
+where
+    I: IntoIterator<Item = S>,
+    I::IntoIter: Send + 'static,
+    S: Into<String>,
+{
+    client.get_many("/validators", addresses, concurrency, order)
+}
+
 /// Get stats for validators
 pub async fn stats(client: &Client) -> Result<ValidatorStats> {
     client.fetch("/validators/stats", NO_QUERY).await
@@ -29,3 +55,31 @@ pub async fn stats(client: &Client) -> Result<ValidatorStats> {
 pub fn rewards(client: &Client, address: &str, query: &QueryTimeRange) -> Stream<Reward> {
     client.fetch_stream(&format!("/validators/{}/rewards", address), query)
 }
+
+/// Get rewards for a validator as pages, starting after the page for the
+/// given cursor if any. The query is ignored when resuming from a cursor.
+pub fn rewards_pages(
+    client: &Client,
+    address: &str,
+    query: &QueryTimeRange,
+    cursor: Option<&str>,
+) -> Stream<Page<Reward>> {
+    client.fetch_pages(&format!("/validators/{}/rewards", address), query, cursor)
+}
+
+/// Get the sum of the rewards for a validator in the given timeframe, or the
+/// sums for each bucket of the given size when a bucket is given
+pub async fn rewards_sum(
+    client: &Client,
+    address: &str,
+    query: &QueryTimeRange,
+    bucket: Option<Bucket>,
+) -> Result<RewardSums> {
+    let query = QueryRewardSum {
+        range: query,
+        bucket,
+    };
+    client
+        .fetch(&format!("/validators/{}/rewards/sum", address), &query)
+        .await
+}
@@ -31,12 +31,10 @@ mod test {
         let vars = vars::get_named(&client, &["txn_fees"])
             .await
             .expect("named vars");
-        assert_eq!(
-            true,
-            vars.get("txn_fees")
-                .expect("txn_fees")
-                .as_bool()
-                .expect("bool")
-        );
+        assert!(vars
+            .get("txn_fees")
+            .expect("txn_fees")
+            .as_bool()
+            .expect("bool"));
     }
 }